@@ -0,0 +1,1096 @@
+//! Headless project creation for custom frontends
+//!
+//! [`ProjectBuilder`] runs the `create` pipeline without prompting: tool check, template
+//! selection, version checks, language resolution, runtime check, writing the files together
+//! with the lock and `.iii/project.ini`, post-create hooks, dependency install and telemetry.
+//! Choices left unset get the answers `create --yes` would give, and failures come back as
+//! [`BuildError`] values a GUI or service can match on, while progress goes to an
+//! [`EventSink`]. The terminal frontend runs the same steps, asking for the choices instead.
+
+use crate::config::UserConfig;
+use crate::events::{Event, EventSink, NoEvents};
+use crate::product::ProductConfig;
+use crate::project::staging::StagedProject;
+use crate::project::{self, lock, lock::ScaffoldLock};
+use crate::runtime::check::{self, Language, RuntimeInfo};
+use crate::telemetry::{self, DependencyInstall};
+use crate::templates::copier::{
+    self, ConflictPolicy, ConflictResolution, RenderedFile, ResolvedWrites,
+};
+use crate::templates::manifest::{
+    LanguageFiles, OptionValues, RootManifest, TemplateManifest, VariableValues,
+};
+use crate::templates::{fetcher::TemplateFetcher, hooks, info, version};
+use serde::Serialize;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Why creating a project failed
+#[derive(Debug, thiserror::Error)]
+pub enum BuildError {
+    /// A tool the product requires is missing (see [`ProjectBuilder::check_tools`])
+    #[error("{tool} is not installed. Install it with: {install_command}")]
+    ToolMissing {
+        tool: String,
+        install_command: String,
+    },
+
+    #[error("Template '{name}' not found. Available templates: {}", .available.join(", "))]
+    TemplateNotFound {
+        name: String,
+        available: Vec<String>,
+    },
+
+    /// The registry has several templates and none was chosen
+    #[error("No template given. Available templates: {}", .available.join(", "))]
+    TemplateRequired { available: Vec<String> },
+
+    #[error("No templates found.")]
+    NoTemplates,
+
+    /// The installed iii engine is older than the template needs
+    #[error("{0}")]
+    IncompatibleEngine(String),
+
+    #[error("No project directory given")]
+    DirectoryRequired,
+
+    #[error("Parent directory does not exist: {}", .0.display())]
+    ParentDirectoryMissing(PathBuf),
+
+    /// Languages or options the template does not offer
+    #[error("{0}")]
+    InvalidSelection(String),
+
+    /// Runtimes needed by the selected languages are not installed
+    #[error("{0}")]
+    MissingRuntimes(String),
+
+    /// Project-relative paths that already exist under [`ConflictPolicy::Fail`]
+    #[error("{} existing file(s) would be overwritten: {}", .0.len(), .0.join(", "))]
+    Conflicts(Vec<String>),
+
+    #[error("Required post-create hook failed: {command}")]
+    HookFailed {
+        command: String,
+        #[source]
+        source: anyhow::Error,
+    },
+
+    /// A step after staging failed; the files this run created were removed again
+    #[error("Rolled back {removed} files created by this run")]
+    RolledBack {
+        removed: usize,
+        #[source]
+        source: Box<BuildError>,
+    },
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// Everything a frontend needs to know about the project `create` produced
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateReport {
+    pub template: String,
+    pub template_version: String,
+    pub project_dir: PathBuf,
+    pub languages: Vec<String>,
+    /// Project-relative paths written (or, for a dry run, that would be written)
+    pub files: Vec<String>,
+    pub runtimes: Vec<RuntimeInfo>,
+    pub dependency_installs: Vec<DependencyInstall>,
+    /// Identifier stored in `.iii/project.ini`; absent for a dry run
+    pub project_id: Option<String>,
+    pub next_steps: Vec<String>,
+    pub dry_run: bool,
+}
+
+impl CreateReport {
+    /// Print the report as pretty JSON on stdout
+    pub fn print(&self) -> anyhow::Result<()> {
+        println!("{}", serde_json::to_string_pretty(self)?);
+        Ok(())
+    }
+}
+
+/// Creates a project without prompts
+///
+/// ```rust,no_run
+/// # async fn example(product: scaffolder_core::ProductDefinition) -> Result<(), scaffolder_core::BuildError> {
+/// use scaffolder_core::ProjectBuilder;
+///
+/// let report = ProjectBuilder::new(product)
+///     .template("quickstart")
+///     .directory("my-app")
+///     .languages(["typescript"])
+///     .create()
+///     .await?;
+/// println!("created {} files", report.files.len());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ProjectBuilder<C: ProductConfig> {
+    config: C,
+    cli_version: String,
+    template_dir: Option<PathBuf>,
+    template: Option<String>,
+    directory: Option<PathBuf>,
+    languages: Option<Vec<String>>,
+    options: OptionValues,
+    variables: VariableValues,
+    on_conflict: ConflictPolicy,
+    check_tools: bool,
+    run_hooks: bool,
+    install_dependencies: bool,
+    user_config: UserConfig,
+    events: Arc<dyn EventSink>,
+}
+
+impl<C: ProductConfig> ProjectBuilder<C> {
+    /// A builder with the `--yes` defaults; existing files are an error until
+    /// [`on_conflict`](Self::on_conflict) says otherwise, and hooks only run after
+    /// [`run_hooks`](Self::run_hooks)
+    pub fn new(config: C) -> Self {
+        Self {
+            config,
+            cli_version: crate::DEFAULT_CLI_VERSION.to_string(),
+            template_dir: None,
+            template: None,
+            directory: None,
+            languages: None,
+            options: OptionValues::new(),
+            variables: VariableValues::new(),
+            on_conflict: ConflictPolicy::Fail,
+            check_tools: true,
+            run_hooks: false,
+            install_dependencies: true,
+            user_config: UserConfig::default(),
+            events: Arc::new(NoEvents),
+        }
+    }
+
+    /// Version checked against the template's version and recorded in the lock
+    pub fn cli_version(mut self, version: impl Into<String>) -> Self {
+        self.cli_version = version.into();
+        self
+    }
+
+    /// Read templates from a local registry instead of the remote one
+    pub fn template_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.template_dir = Some(path.into());
+        self
+    }
+
+    /// Template to use; may be left out when the registry has a single template
+    pub fn template(mut self, name: impl Into<String>) -> Self {
+        self.template = Some(name.into());
+        self
+    }
+
+    /// Project directory, relative to the working directory unless absolute
+    pub fn directory(mut self, path: impl Into<PathBuf>) -> Self {
+        self.directory = Some(path.into());
+        self
+    }
+
+    /// Optional languages to include (required ones are always added); defaults to the
+    /// preferred languages from the user config, or every language the template offers
+    pub fn languages<I, S>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.languages = Some(languages.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set a template option; options left unset take their defaults
+    pub fn option(mut self, name: impl Into<String>, value: bool) -> Self {
+        self.options.insert(name.into(), value);
+        self
+    }
+
+    /// Set a template variable; variables left unset take their defaults
+    pub fn variable(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.variables.insert(name.into(), value.into());
+        self
+    }
+
+    /// How to handle files that already exist; [`ConflictPolicy::Prompt`] acts like `Fail`
+    pub fn on_conflict(mut self, policy: ConflictPolicy) -> Self {
+        self.on_conflict = policy;
+        self
+    }
+
+    /// Whether to require the product's tools and the template's minimum iii engine version
+    pub fn check_tools(mut self, check: bool) -> Self {
+        self.check_tools = check;
+        self
+    }
+
+    /// Whether to run the template's post-create hooks; they run commands from the
+    /// template, so this is off unless the caller opts in
+    pub fn run_hooks(mut self, run: bool) -> Self {
+        self.run_hooks = run;
+        self
+    }
+
+    /// Whether to install dependencies after writing the project
+    pub fn install_dependencies(mut self, install: bool) -> Self {
+        self.install_dependencies = install;
+        self
+    }
+
+    /// Settings from the config files (see [`UserConfig::load`]); none are read by default
+    pub fn user_config(mut self, user_config: UserConfig) -> Self {
+        self.user_config = user_config;
+        self
+    }
+
+    /// Where progress events go; they are dropped by default
+    pub fn events(mut self, sink: impl EventSink + 'static) -> Self {
+        self.events = Arc::new(sink);
+        self
+    }
+
+    /// Resolve every choice and report the files that would be written, without writing
+    pub async fn dry_run(self) -> Result<CreateReport, BuildError> {
+        let events = self.events.as_ref();
+        let (mut fetcher, template, project_dir, languages, options, variables) =
+            self.resolve().await?;
+        let runtimes = required_runtimes(&template.manifest, &languages, events)?;
+        let plan = ProjectPlan {
+            template_name: &template.name,
+            manifest: &template.manifest,
+            language_files: &template.language_files,
+            project_dir: &project_dir,
+            selected_languages: &languages,
+            options: &options,
+            variables: &variables,
+        };
+        let rendered = plan.render(&mut fetcher, events).await?;
+
+        let mut report = plan.report(runtimes, true);
+        report.files = copier::plan_writes(&project_dir, &rendered)
+            .into_iter()
+            .map(|p| p.dest)
+            .collect();
+        Ok(report)
+    }
+
+    /// Create the project
+    pub async fn create(self) -> Result<CreateReport, BuildError> {
+        if self.check_tools && self.config.requires_iii() {
+            let tool = crate::runtime::tool::iii_tool();
+            if !tool.is_installed() {
+                return Err(BuildError::ToolMissing {
+                    tool: tool.config().display_name.to_string(),
+                    install_command: tool.install_command(),
+                });
+            }
+        }
+
+        let events = self.events.as_ref();
+        let (mut fetcher, template, project_dir, languages, options, variables) =
+            self.resolve().await?;
+        let runtimes = required_runtimes(&template.manifest, &languages, events)?;
+        let plan = ProjectPlan {
+            template_name: &template.name,
+            manifest: &template.manifest,
+            language_files: &template.language_files,
+            project_dir: &project_dir,
+            selected_languages: &languages,
+            options: &options,
+            variables: &variables,
+        };
+        let rendered = plan.render(&mut fetcher, events).await?;
+        let merge_known = self.on_conflict != ConflictPolicy::Overwrite;
+        let resolved =
+            resolve_writes(&project_dir, &rendered, self.on_conflict, merge_known).await?;
+
+        let package_manager = self.user_config.package_manager.unwrap_or_default();
+        let written = write_project(
+            &plan,
+            &resolved,
+            &fetcher.source().redacted(),
+            &self.cli_version,
+            events,
+            || async {
+                if self.run_hooks {
+                    for hook in hooks::applicable_hooks(&template.manifest, &languages, &options) {
+                        match hooks::run_hook(&project_dir, hook, events).await {
+                            Ok(()) => {}
+                            Err(source) if hook.required => {
+                                return Err(BuildError::HookFailed {
+                                    command: hook.run.clone(),
+                                    source,
+                                });
+                            }
+                            // Optional hooks only help with setup; the project is usable without them
+                            Err(_) => {}
+                        }
+                    }
+                }
+                if !self.install_dependencies {
+                    return Ok(Vec::new());
+                }
+                Ok(telemetry::run_dependency_install(
+                    &project_dir,
+                    &languages,
+                    package_manager,
+                    events,
+                )
+                .await?)
+            },
+        )
+        .await?;
+
+        if self.user_config.telemetry_enabled() {
+            send_project_created(
+                self.config.name(),
+                &self.cli_version,
+                &plan,
+                &written.project_id,
+                events,
+            )
+            .await;
+        }
+        report_merges(&resolved, events);
+
+        let mut report = plan.report(runtimes, false);
+        report.files = resolved.files.iter().map(|f| f.dest.clone()).collect();
+        report.dependency_installs = written.finished;
+        report.project_id = Some(written.project_id);
+        Ok(report)
+    }
+
+    /// Fetch the template and settle directory, languages, options and variables
+    async fn resolve(
+        &self,
+    ) -> Result<
+        (
+            TemplateFetcher,
+            SelectedTemplate,
+            PathBuf,
+            Vec<Language>,
+            OptionValues,
+            VariableValues,
+        ),
+        BuildError,
+    > {
+        let mut fetcher = match &self.template_dir {
+            Some(path) => TemplateFetcher::from_local(path.clone(), self.config.user_agent()),
+            None => TemplateFetcher::new(
+                crate::TemplateSource::from_user_config(&self.config, &self.user_config)?,
+                self.config.user_agent(),
+            )
+            .with_auth(self.user_config.registry_tokens()),
+        };
+
+        let root = fetcher.fetch_root_manifest().await?;
+        let name = match &self.template {
+            Some(name) => name.clone(),
+            None => match root.templates.as_slice() {
+                [] => return Err(BuildError::NoTemplates),
+                [only] => only.clone(),
+                available => {
+                    return Err(BuildError::TemplateRequired {
+                        available: available.to_vec(),
+                    })
+                }
+            },
+        };
+        let template = fetch_template(&mut fetcher, &root, &name, self.events.as_ref()).await?;
+
+        if self.check_tools {
+            check_engine(&template.manifest)?;
+        }
+
+        let directory = self
+            .directory
+            .as_deref()
+            .ok_or(BuildError::DirectoryRequired)?;
+        let project_dir = resolve_directory(directory)?;
+
+        let requested = self.languages.clone().or_else(|| {
+            let preferred = self.user_config.preferred_languages();
+            (!preferred.is_empty()).then(|| {
+                lock::language_names(&preferred)
+                    .into_iter()
+                    .filter(|name| {
+                        template.manifest.is_required(name) || template.manifest.is_optional(name)
+                    })
+                    .collect()
+            })
+        });
+        let languages = info::resolve_languages(&template.manifest, requested.as_deref())
+            .map_err(|e| BuildError::InvalidSelection(format!("{:#}", e)))?;
+        if languages.is_empty() {
+            return Err(BuildError::InvalidSelection(
+                "No languages available for this template.".to_string(),
+            ));
+        }
+
+        let options = template
+            .manifest
+            .resolve_options(&self.options)
+            .map_err(|e| BuildError::InvalidSelection(format!("{:#}", e)))?;
+        let variables = template
+            .manifest
+            .resolve_variables(&self.variables)
+            .map_err(|e| BuildError::InvalidSelection(format!("{:#}", e)))?;
+
+        Ok((
+            fetcher,
+            template,
+            project_dir,
+            languages,
+            options,
+            variables,
+        ))
+    }
+}
+
+/// A template picked from the registry, with the language files that apply to it
+pub(crate) struct SelectedTemplate {
+    pub(crate) name: String,
+    pub(crate) manifest: TemplateManifest,
+    pub(crate) language_files: LanguageFiles,
+}
+
+/// Everything resolved about the project before any files are written
+#[derive(Clone, Copy)]
+pub(crate) struct ProjectPlan<'a> {
+    pub(crate) template_name: &'a str,
+    pub(crate) manifest: &'a TemplateManifest,
+    pub(crate) language_files: &'a LanguageFiles,
+    pub(crate) project_dir: &'a Path,
+    pub(crate) selected_languages: &'a [Language],
+    pub(crate) options: &'a OptionValues,
+    pub(crate) variables: &'a VariableValues,
+}
+
+impl ProjectPlan<'_> {
+    /// Render the template's files for the selected languages, options and variables
+    pub(crate) async fn render(
+        &self,
+        fetcher: &mut TemplateFetcher,
+        events: &dyn EventSink,
+    ) -> anyhow::Result<Vec<RenderedFile>> {
+        events.emit(Event::RenderStarted);
+        let mut rendered = copier::render_template(
+            fetcher,
+            self.template_name,
+            self.manifest,
+            self.selected_languages,
+            self.options,
+            self.language_files,
+        )
+        .await?;
+        copier::apply_variables(&mut rendered, self.variables)?;
+        events.emit(Event::TemplateRendered {
+            files: rendered.len(),
+        });
+        Ok(rendered)
+    }
+
+    /// A report without the outcome of writing: no files, installs or project id yet
+    pub(crate) fn report(&self, runtimes: Vec<RuntimeInfo>, dry_run: bool) -> CreateReport {
+        CreateReport {
+            template: self.template_name.to_string(),
+            template_version: self.manifest.version.clone(),
+            project_dir: self.project_dir.to_path_buf(),
+            languages: lock::language_names(self.selected_languages),
+            files: Vec::new(),
+            runtimes,
+            dependency_installs: Vec::new(),
+            project_id: None,
+            next_steps: next_steps(self.project_dir, self.manifest),
+            dry_run,
+        }
+    }
+
+    /// Directory name, recorded as the project name
+    pub(crate) fn project_name(&self) -> String {
+        self.project_dir
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string()
+    }
+}
+
+/// Fetch a template's manifest, checking the registry lists it
+pub(crate) async fn fetch_template(
+    fetcher: &mut TemplateFetcher,
+    root: &RootManifest,
+    name: &str,
+    events: &dyn EventSink,
+) -> Result<SelectedTemplate, BuildError> {
+    if !root.templates.iter().any(|t| t == name) {
+        return Err(BuildError::TemplateNotFound {
+            name: name.to_string(),
+            available: root.templates.clone(),
+        });
+    }
+
+    let manifest = fetcher.fetch_template_manifest(name).await?;
+    let language_files = root.language_files_for(&manifest);
+    events.emit(Event::TemplateFetched {
+        template: name.to_string(),
+        version: manifest.version.clone(),
+    });
+    Ok(SelectedTemplate {
+        name: name.to_string(),
+        manifest,
+        language_files,
+    })
+}
+
+/// Check the installed iii engine against the template's minimum; returns
+/// `(installed, required)` when the template sets one
+pub(crate) fn check_engine(
+    manifest: &TemplateManifest,
+) -> Result<Option<(String, String)>, BuildError> {
+    let Some(min_version) = &manifest.min_iii_version else {
+        return Ok(None);
+    };
+    let installed =
+        version::check_iii_engine_version(min_version).map_err(BuildError::IncompatibleEngine)?;
+    Ok(Some((installed, min_version.clone())))
+}
+
+/// Make `directory` absolute and check its parent exists
+pub(crate) fn resolve_directory(directory: &Path) -> Result<PathBuf, BuildError> {
+    let path = if directory.is_absolute() {
+        directory.to_path_buf()
+    } else if directory.as_os_str().is_empty() || directory == Path::new(".") {
+        std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+    } else {
+        std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join(directory)
+    };
+
+    if let Some(parent) = path.parent() {
+        if !parent.exists() && parent != Path::new("") {
+            return Err(BuildError::ParentDirectoryMissing(parent.to_path_buf()));
+        }
+    }
+
+    Ok(path)
+}
+
+/// Check runtimes for the selected languages; languages the template merely includes
+/// are reported but don't fail the check
+pub(crate) fn required_runtimes(
+    manifest: &TemplateManifest,
+    selected_languages: &[Language],
+    events: &dyn EventSink,
+) -> Result<Vec<RuntimeInfo>, BuildError> {
+    let advisory: Vec<Language> = manifest
+        .included_language_names()
+        .iter()
+        .filter_map(|s| Language::parse(s))
+        .filter(|l| selected_languages.contains(l))
+        .collect();
+
+    events.emit(Event::RuntimeCheckStarted);
+    match check::check_runtimes_with_advisory(selected_languages, &advisory) {
+        Ok(runtimes) => {
+            for runtime in &runtimes {
+                events.emit(Event::RuntimeDetected {
+                    runtime: runtime.clone(),
+                });
+            }
+            events.emit(Event::RuntimeCheckFinished { missing: None });
+            Ok(runtimes)
+        }
+        Err(e) => {
+            let missing = format!("{:#}", e);
+            events.emit(Event::RuntimeCheckFinished {
+                missing: Some(missing.clone()),
+            });
+            Err(BuildError::MissingRuntimes(missing))
+        }
+    }
+}
+
+/// Decide how to handle existing files under a fixed policy; `Prompt` is treated as `Fail`
+///
+/// Well-known files are merged with what is there unless `merge_known` is off, which
+/// callers use for an explicitly chosen `Overwrite`.
+pub(crate) async fn resolve_writes(
+    project_dir: &Path,
+    rendered: &[RenderedFile],
+    policy: ConflictPolicy,
+    merge_known: bool,
+) -> Result<ResolvedWrites, BuildError> {
+    let fixed = |resolution: ConflictResolution| move |_: &str, _: &[u8], _: &[u8]| Ok(resolution);
+    let resolution = match policy {
+        ConflictPolicy::Overwrite => ConflictResolution::Overwrite,
+        ConflictPolicy::Skip => ConflictResolution::Skip,
+        ConflictPolicy::Backup => ConflictResolution::Backup,
+        ConflictPolicy::Fail | ConflictPolicy::Prompt => {
+            let conflicts = copier::find_conflicts(project_dir, rendered);
+            if !conflicts.is_empty() {
+                return Err(BuildError::Conflicts(conflicts));
+            }
+            ConflictResolution::Overwrite
+        }
+    };
+    Ok(copier::resolve_writes(project_dir, rendered, merge_known, fixed(resolution)).await?)
+}
+
+/// What [`write_project`] did
+pub(crate) struct WrittenProject<T> {
+    pub(crate) project_id: String,
+    /// Result of the `finish` step
+    pub(crate) finished: T,
+}
+
+/// Stage the files with the base snapshot, lock and `project.ini`, move them into place,
+/// then run `finish` (hooks and dependency install). A failure at any point removes exactly
+/// the files this run created and comes back as [`BuildError::RolledBack`].
+pub(crate) async fn write_project<T, F, Fut>(
+    plan: &ProjectPlan<'_>,
+    resolved: &ResolvedWrites,
+    registry: &str,
+    cli_version: &str,
+    events: &dyn EventSink,
+    finish: F,
+) -> Result<WrittenProject<T>, BuildError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, BuildError>>,
+{
+    let lock = ScaffoldLock {
+        template: plan.template_name.to_string(),
+        template_version: plan.manifest.version.clone(),
+        registry: registry.to_string(),
+        cli_version: cli_version.to_string(),
+        languages: lock::language_names(plan.selected_languages),
+        options: plan.options.clone(),
+        variables: plan.variables.clone(),
+        files: lock::hash_files(&resolved.template_files),
+    };
+    let project_id = uuid::Uuid::new_v4().to_string();
+
+    for path in &resolved.skipped {
+        events.emit(Event::FileSkipped { path: path.clone() });
+    }
+    for (path, backup) in &resolved.backups {
+        events.emit(Event::FileBackedUp {
+            path: path.clone(),
+            backup: backup.clone(),
+        });
+    }
+
+    events.emit(Event::WriteStarted {
+        project_dir: plan.project_dir.to_path_buf(),
+    });
+    let staged = StagedProject::begin(plan.project_dir)?;
+    let outcome: Result<T, BuildError> = async {
+        staged.stage(&resolved.files)?;
+
+        let staging_dir = staged.dir();
+        // The template's content is the merge base for future upgrades, so lines merged in
+        // from existing files stay local changes; skipped files stay the user's own
+        project::write_base_snapshot(&staging_dir, &resolved.template_files).await?;
+        lock.write(&staging_dir).await?;
+        telemetry::write_project_ini(
+            &staging_dir,
+            &project_id,
+            &plan.project_name(),
+            plan.template_name,
+            &plan.manifest.version,
+        )
+        .await?;
+
+        staged.commit(&resolved.backups)?;
+        // Dependency installs and hooks (which often install too) create these; rollback
+        // removes the ones that do not exist yet, and leaves anything else the user adds
+        let install_dirs = telemetry::install_directories(plan.project_dir)
+            .into_iter()
+            .map(|dir| plan.project_dir.join(dir))
+            .chain(
+                plan.manifest
+                    .post_create
+                    .iter()
+                    .filter_map(|hook| hooks::hook_dir(plan.project_dir, hook).ok()),
+            )
+            .collect::<Vec<_>>();
+        staged.claim(install_dirs.iter().flat_map(|dir| {
+            telemetry::INSTALL_OUTPUTS
+                .iter()
+                .map(move |output| dir.join(output))
+        }));
+        for file in &resolved.files {
+            events.emit(Event::FileWritten {
+                path: file.dest.clone(),
+            });
+        }
+        events.emit(Event::ProjectWritten {
+            project_dir: plan.project_dir.to_path_buf(),
+            files: resolved.files.len(),
+        });
+
+        finish().await
+    }
+    .await;
+
+    match outcome {
+        Ok(finished) => {
+            staged.finish();
+            Ok(WrittenProject {
+                project_id,
+                finished,
+            })
+        }
+        Err(source) => {
+            let removed = staged.rollback();
+            events.emit(Event::RolledBack { removed });
+            Err(BuildError::RolledBack {
+                removed,
+                source: Box::new(source),
+            })
+        }
+    }
+}
+
+/// Report files merged with what the user already had; frontends show these last
+pub(crate) fn report_merges(resolved: &ResolvedWrites, events: &dyn EventSink) {
+    for (path, summary) in &resolved.merged {
+        events.emit(Event::FileMerged {
+            path: path.clone(),
+            summary: summary.clone(),
+        });
+    }
+}
+
+/// Send the `project_created` event, waiting at most 2s for it to go out
+pub(crate) async fn send_project_created(
+    product_name: &'static str,
+    cli_version: &str,
+    plan: &ProjectPlan<'_>,
+    project_id: &str,
+    events: &dyn EventSink,
+) {
+    let handle = telemetry::spawn_project_event(
+        "project_created",
+        telemetry::platform_for_product(product_name),
+        cli_version.to_string(),
+        serde_json::json!({
+            "project_id": project_id,
+            "project_name": plan.project_name(),
+            "template": plan.template_name,
+            "product": product_name,
+        }),
+    );
+
+    // Best-effort flush: wait up to 2s for telemetry to complete, then move on
+    if let Some(handle) = handle {
+        let completed = tokio::time::timeout(std::time::Duration::from_secs(2), handle)
+            .await
+            .is_ok();
+        events.emit(Event::TelemetrySent {
+            name: "project_created".to_string(),
+            completed,
+        });
+    }
+}
+
+/// Steps to show after creation: `cd` into the project, then the template's own steps
+pub(crate) fn next_steps(project_dir: &Path, manifest: &TemplateManifest) -> Vec<String> {
+    let mut steps: Vec<String> = Vec::new();
+
+    if let Ok(current) = std::env::current_dir() {
+        if current != *project_dir {
+            steps.push(format!("cd {}", project_dir.display()));
+        }
+    }
+
+    steps.extend(manifest.next_steps.iter().cloned());
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProductDefinition;
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let full = dir.join(path);
+        std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+        std::fs::write(full, content).unwrap();
+    }
+
+    fn product() -> ProductDefinition {
+        ProductDefinition::from_yaml(
+            "name: acme\ndisplay_name: Acme\ndescription: Acme scaffolder\ntemplate_url: https://templates.example.com/\ntemplate_url_env: ACME_TEMPLATE_URL\ndocs_url: https://example.com/docs\nupgrade_command: cargo install acme\n",
+        )
+        .unwrap()
+    }
+
+    /// A registry with two templates; `api` needs TypeScript, which is advisory so the
+    /// runtime check passes without Node.js
+    fn registry() -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        write(
+            dir,
+            "template.yaml",
+            "templates: [api, docs]\nlanguage_files:\n  common: [README.md]\n  typescript: ['*.ts']\n",
+        );
+        write(
+            dir,
+            "api/template.yaml",
+            "name: API\ndescription: API service\nversion: '0.1.0'\nrequires: [typescript]\ntreat_required_as_included: true\nfiles:\n  - README.md\n  - src/index.ts\nnext_steps:\n  - npm run dev\n",
+        );
+        write(dir, "api/README.md", "# API\n");
+        write(dir, "api/src/index.ts", "export {}\n");
+        write(
+            dir,
+            "docs/template.yaml",
+            "name: Docs\ndescription: Docs site\nversion: '0.1.0'\nrequires: [typescript]\ntreat_required_as_included: true\nfiles:\n  - README.md\n",
+        );
+        write(dir, "docs/README.md", "# Docs\n");
+        tmp
+    }
+
+    fn builder(registry: &Path, project_dir: &Path) -> ProjectBuilder<ProductDefinition> {
+        ProjectBuilder::new(product())
+            .template_dir(registry)
+            .template("api")
+            .directory(project_dir)
+            .check_tools(false)
+            .install_dependencies(false)
+            .user_config(UserConfig {
+                telemetry: Some(false),
+                ..Default::default()
+            })
+    }
+
+    #[tokio::test]
+    async fn create_writes_files_lock_and_project_ini() {
+        let registry = registry();
+        let out = tempfile::tempdir().unwrap();
+        let project_dir = out.path().join("app");
+
+        let report = builder(registry.path(), &project_dir)
+            .create()
+            .await
+            .unwrap();
+
+        assert_eq!(report.template, "api");
+        assert_eq!(report.languages, vec!["typescript"]);
+        assert_eq!(report.files, vec!["README.md", "src/index.ts"]);
+        assert!(!report.dry_run);
+        assert!(report.next_steps.contains(&"npm run dev".to_string()));
+        assert!(project_dir.join("src/index.ts").is_file());
+        assert!(project_dir.join(lock::LOCK_FILE).is_file());
+        let ini = std::fs::read_to_string(project_dir.join(".iii/project.ini")).unwrap();
+        assert!(ini.contains(&format!("project_id={}", report.project_id.unwrap())));
+    }
+
+    #[tokio::test]
+    async fn dry_run_writes_nothing() {
+        let registry = registry();
+        let out = tempfile::tempdir().unwrap();
+        let project_dir = out.path().join("app");
+
+        let report = builder(registry.path(), &project_dir)
+            .dry_run()
+            .await
+            .unwrap();
+
+        assert!(report.dry_run);
+        assert_eq!(report.files, vec!["README.md", "src/index.ts"]);
+        assert_eq!(report.project_id, None);
+        assert!(!project_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn selection_errors_are_structured() {
+        let registry = registry();
+        let out = tempfile::tempdir().unwrap();
+
+        let err = builder(registry.path(), out.path())
+            .template("web")
+            .create()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            BuildError::TemplateNotFound { ref name, ref available }
+                if name == "web" && available == &["api", "docs"]
+        ));
+
+        let err = ProjectBuilder::new(product())
+            .template_dir(registry.path())
+            .directory(out.path())
+            .check_tools(false)
+            .dry_run()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BuildError::TemplateRequired { .. }));
+
+        let err = builder(registry.path(), out.path())
+            .languages(["python"])
+            .dry_run()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BuildError::InvalidSelection(_)));
+
+        let err = builder(registry.path(), &out.path().join("missing/app"))
+            .dry_run()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BuildError::ParentDirectoryMissing(_)));
+    }
+
+    #[tokio::test]
+    async fn existing_files_fail_unless_a_policy_allows_them() {
+        let registry = registry();
+        let out = tempfile::tempdir().unwrap();
+        write(out.path(), "README.md", "mine\n");
+
+        let err = builder(registry.path(), out.path())
+            .create()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BuildError::Conflicts(ref paths) if paths == &["README.md"]));
+        assert!(!out.path().join("src").exists());
+
+        builder(registry.path(), out.path())
+            .on_conflict(ConflictPolicy::Skip)
+            .create()
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(out.path().join("README.md")).unwrap(),
+            "mine\n"
+        );
+        assert!(out.path().join("src/index.ts").is_file());
+    }
+
+    #[tokio::test]
+    async fn skipped_files_leave_the_project_clean() {
+        let registry = registry();
+        let out = tempfile::tempdir().unwrap();
+        write(out.path(), "README.md", "mine\n");
+
+        builder(registry.path(), out.path())
+            .on_conflict(ConflictPolicy::Skip)
+            .create()
+            .await
+            .unwrap();
+
+        let lock = lock::ScaffoldLock::read(out.path()).await.unwrap();
+        assert!(!lock.files.contains_key("README.md"));
+        let status = lock.status(out.path()).await;
+        assert!(!status.is_empty());
+        assert!(status
+            .iter()
+            .all(|file| file.state == lock::FileState::Unchanged));
+        assert!(project::read_base_file(out.path(), "README.md")
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn upgrade_keeps_lines_merged_into_existing_files() {
+        let registry = registry();
+        write(
+            registry.path(),
+            "template.yaml",
+            "templates: [api, docs]\nlanguage_files:\n  common: [README.md, .gitignore]\n  typescript: ['*.ts']\n",
+        );
+        write(
+            registry.path(),
+            "api/template.yaml",
+            "name: API\ndescription: API service\nversion: '0.1.0'\nrequires: [typescript]\ntreat_required_as_included: true\nfiles:\n  - README.md\n  - .gitignore\n  - src/index.ts\n",
+        );
+        write(registry.path(), "api/.gitignore", "node_modules/\n");
+        let out = tempfile::tempdir().unwrap();
+        write(out.path(), ".gitignore", "secrets.env\n");
+
+        builder(registry.path(), out.path()).create().await.unwrap();
+        assert_eq!(
+            project::read_base_file(out.path(), ".gitignore").await,
+            Some(b"node_modules/\n".to_vec())
+        );
+
+        // The next template version adds a line; the user's line survives the upgrade
+        let next = [RenderedFile {
+            dest: ".gitignore".to_string(),
+            content: b"node_modules/\ndist/\n".to_vec(),
+        }];
+        let labels = crate::project::merge::MergeLabels {
+            ours: "local",
+            base: "api 0.1.0",
+            theirs: "api 0.2.0",
+        };
+        crate::project::upgrade::upgrade_project(out.path(), &next, labels)
+            .await
+            .unwrap();
+        let gitignore = std::fs::read_to_string(out.path().join(".gitignore")).unwrap();
+        assert!(gitignore.contains("secrets.env"), "{}", gitignore);
+        assert!(gitignore.contains("dist/"), "{}", gitignore);
+    }
+
+    #[test]
+    fn report_serializes_with_stable_field_names() {
+        let report = CreateReport {
+            template: "quickstart".to_string(),
+            template_version: "0.1.0".to_string(),
+            project_dir: PathBuf::from("/tmp/app"),
+            languages: vec!["typescript".to_string()],
+            files: vec!["package.json".to_string()],
+            runtimes: vec![RuntimeInfo {
+                name: "Node.js",
+                version: Some("v20.0.0".to_string()),
+                available: true,
+            }],
+            dependency_installs: vec![DependencyInstall {
+                directory: ".".to_string(),
+                command: "npm install".to_string(),
+                success: true,
+            }],
+            project_id: Some("abc".to_string()),
+            next_steps: vec!["cd /tmp/app".to_string()],
+            dry_run: false,
+        };
+
+        let value = serde_json::to_value(&report).unwrap();
+        assert_eq!(value["template"], "quickstart");
+        assert_eq!(value["project_dir"], "/tmp/app");
+        assert_eq!(value["runtimes"][0]["version"], "v20.0.0");
+        assert_eq!(value["dependency_installs"][0]["directory"], ".");
+        assert_eq!(value["project_id"], "abc");
+        let keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "template",
+                "template_version",
+                "project_dir",
+                "languages",
+                "files",
+                "runtimes",
+                "dependency_installs",
+                "project_id",
+                "next_steps",
+                "dry_run"
+            ]
+        );
+    }
+}
@@ -0,0 +1,523 @@
+//! Command-line interface shared by every product binary
+//!
+//! A product binary only supplies its `ProductConfig` (a Rust impl or a YAML
+//! [`ProductDefinition`](crate::product::ProductDefinition)) and calls [`main`].
+
+use crate::commands::{
+    AddWorkerArgs, CreateArgs, OutputFormat, StatusArgs, TemplateNewArgs, UpgradeArgs,
+};
+use crate::product::ProductConfig;
+use crate::templates::copier::ConflictPolicy;
+use crate::templates::{ExtractArgs, InfoArgs};
+use crate::ui;
+use anyhow::Result;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Top-level arguments; name, about text and version come from the product at runtime
+#[derive(Parser, Debug)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Create a new project
+    Create(CliCreateArgs),
+    /// Add template content to an existing project
+    #[command(subcommand)]
+    Add(AddCommand),
+    /// Upgrade a project to the latest version of its template, merging local changes
+    ///
+    /// Local changes are merged against `.iii/base`, a copy of the template files as last
+    /// written; commit it with the project. Without it, the template version recorded in
+    /// `.iii/project.ini` is fetched as the merge base.
+    Upgrade(CliUpgradeArgs),
+    /// List the available templates
+    List(ListArgs),
+    /// Show a template's manifest and the files it would generate
+    Info(CliInfoArgs),
+    /// Show which generated files have been modified or deleted since scaffolding
+    Status(CliStatusArgs),
+    /// Check a template registry for broken manifests, missing files and other problems
+    Validate(ValidateArgs),
+    /// Author templates
+    #[command(subcommand)]
+    Template(TemplateCommand),
+    /// Build zip files for all templates in the template directory (for development use)
+    BuildZips(BuildZipsArgs),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AddCommand {
+    /// Copy a worker folder from a template into the project's workers/ directory
+    Worker(CliAddWorkerArgs),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TemplateCommand {
+    /// Turn an existing project into a template folder with a generated template.yaml
+    Extract(CliExtractArgs),
+    /// Create a new template in a registry and add it to the root template.yaml
+    New(CliTemplateNewArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliCreateArgs {
+    /// Local directory to use for templates instead of fetching from remote (for development use)
+    #[arg(long = "template-dir")]
+    pub template_dir: Option<PathBuf>,
+
+    /// Template name to use
+    #[arg(short, long)]
+    pub template: Option<String>,
+
+    /// Project directory to create
+    #[arg(short, long)]
+    pub directory: Option<PathBuf>,
+
+    /// Languages to include (comma-separated: ts,js,py or typescript,javascript,python)
+    #[arg(short, long, value_delimiter = ',')]
+    pub languages: Option<Vec<String>>,
+
+    /// Set a template option (repeatable: --option docker=true --option redis=false)
+    #[arg(long = "option", value_name = "NAME=BOOL")]
+    pub options: Vec<String>,
+
+    /// Set a template variable (repeatable: --var service=billing)
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    pub variables: Vec<String>,
+
+    /// Skip iii installation check
+    #[arg(long = "skip-iii")]
+    pub skip_iii: bool,
+
+    /// Skip the template's post-create hooks
+    #[arg(long = "no-hooks")]
+    pub no_hooks: bool,
+
+    /// Run the template's post-create hooks without asking (--yes does not run them)
+    #[arg(long = "allow-hooks", conflicts_with = "no_hooks")]
+    pub allow_hooks: bool,
+
+    /// How to handle files that already exist (default: prompt, or overwrite with --yes)
+    #[arg(long = "on-conflict", value_enum)]
+    pub on_conflict: Option<ConflictPolicy>,
+
+    /// Print the files that would be written without creating anything
+    #[arg(long = "dry-run")]
+    pub dry_run: bool,
+
+    /// Auto-confirm all prompts (non-interactive mode)
+    #[arg(short, long)]
+    pub yes: bool,
+
+    /// Read template, directory, languages and options from a YAML answers file
+    #[arg(long, value_name = "FILE")]
+    pub answers: Option<PathBuf>,
+
+    /// Write the choices made to a YAML answers file for later `--answers` runs
+    #[arg(long = "save-answers", value_name = "FILE")]
+    pub save_answers: Option<PathBuf>,
+
+    /// Result format; `json` prints a report on stdout and sends everything else to stderr
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: OutputFormat,
+}
+
+impl From<CliCreateArgs> for CreateArgs {
+    fn from(args: CliCreateArgs) -> Self {
+        CreateArgs {
+            template_dir: args.template_dir,
+            template: args.template,
+            directory: args.directory,
+            languages: args.languages,
+            options: args.options,
+            variables: args.variables,
+            skip_tool_check: args.skip_iii,
+            no_hooks: args.no_hooks,
+            allow_hooks: args.allow_hooks,
+            on_conflict: args.on_conflict,
+            dry_run: args.dry_run,
+            yes: args.yes,
+            answers: args.answers,
+            save_answers: args.save_answers,
+            output: args.output,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CliAddWorkerArgs {
+    /// Worker to copy, as <template>/<worker-dir> (e.g. quickstart/math-worker)
+    #[arg(long)]
+    pub from: String,
+
+    /// Name for the new worker (defaults to the source worker's directory name)
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// Project directory to add the worker to (defaults to the current directory)
+    #[arg(short, long)]
+    pub directory: Option<PathBuf>,
+
+    /// Set a template option (repeatable); defaults to the project's values in .iii/scaffold.lock
+    #[arg(long = "option", value_name = "NAME=BOOL")]
+    pub options: Vec<String>,
+
+    /// Set a template variable (repeatable); defaults to the project's values in .iii/scaffold.lock
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    pub variables: Vec<String>,
+
+    /// Local directory to use for templates instead of fetching from remote (for development use)
+    #[arg(long = "template-dir")]
+    pub template_dir: Option<PathBuf>,
+}
+
+impl From<CliAddWorkerArgs> for AddWorkerArgs {
+    fn from(args: CliAddWorkerArgs) -> Self {
+        AddWorkerArgs {
+            template_dir: args.template_dir,
+            from: args.from,
+            name: args.name,
+            directory: args.directory,
+            options: args.options,
+            variables: args.variables,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CliUpgradeArgs {
+    /// Project directory to upgrade (defaults to the current directory)
+    #[arg(short, long)]
+    pub directory: Option<PathBuf>,
+
+    /// Languages the project uses (detected from the project when omitted)
+    #[arg(short, long, value_delimiter = ',')]
+    pub languages: Option<Vec<String>>,
+
+    /// Set a template option (repeatable: --option docker=true --option redis=false)
+    #[arg(long = "option", value_name = "NAME=BOOL")]
+    pub options: Vec<String>,
+
+    /// Set a template variable (repeatable); defaults to the project's values in .iii/scaffold.lock
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    pub variables: Vec<String>,
+
+    /// Local directory to use for templates instead of fetching from remote (for development use)
+    #[arg(long = "template-dir")]
+    pub template_dir: Option<PathBuf>,
+
+    /// Auto-confirm all prompts (non-interactive mode)
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+impl From<CliUpgradeArgs> for UpgradeArgs {
+    fn from(args: CliUpgradeArgs) -> Self {
+        UpgradeArgs {
+            template_dir: args.template_dir,
+            directory: args.directory,
+            languages: args.languages,
+            options: args.options,
+            variables: args.variables,
+            yes: args.yes,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CliStatusArgs {
+    /// Project directory to inspect (defaults to the current directory)
+    #[arg(short, long)]
+    pub directory: Option<PathBuf>,
+}
+
+impl From<CliStatusArgs> for StatusArgs {
+    fn from(args: CliStatusArgs) -> Self {
+        StatusArgs {
+            directory: args.directory,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct ListArgs {
+    /// Print the templates as a JSON array
+    #[arg(long)]
+    pub json: bool,
+
+    /// Local directory to use for templates instead of fetching from remote (for development use)
+    #[arg(long = "template-dir")]
+    pub template_dir: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliInfoArgs {
+    /// Template name to describe
+    pub template: String,
+
+    /// Languages to plan the file list for (defaults to every language the template offers)
+    #[arg(short, long, value_delimiter = ',')]
+    pub languages: Option<Vec<String>>,
+
+    /// Set a template option for the file list (repeatable: --option docker=true)
+    #[arg(long = "option", value_name = "NAME=BOOL")]
+    pub options: Vec<String>,
+
+    /// Local directory to use for templates instead of fetching from remote (for development use)
+    #[arg(long = "template-dir")]
+    pub template_dir: Option<PathBuf>,
+}
+
+impl From<CliInfoArgs> for InfoArgs {
+    fn from(args: CliInfoArgs) -> Self {
+        InfoArgs {
+            template_dir: args.template_dir,
+            template: args.template,
+            languages: args.languages,
+            options: args.options,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct ValidateArgs {
+    /// Template registry directory to validate (the one containing the root template.yaml)
+    #[arg(long = "template-dir")]
+    pub template_dir: PathBuf,
+
+    /// Print the diagnostics as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliExtractArgs {
+    /// Project directory to extract from
+    pub project_dir: PathBuf,
+
+    /// Template folder to create (usually <registry>/<template-name>)
+    pub out_dir: PathBuf,
+
+    /// Template display name (defaults to the project directory name)
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// Local directory to use for templates instead of fetching from remote (for development use)
+    #[arg(long = "template-dir")]
+    pub template_dir: Option<PathBuf>,
+}
+
+impl From<CliExtractArgs> for ExtractArgs {
+    fn from(args: CliExtractArgs) -> Self {
+        ExtractArgs {
+            project_dir: args.project_dir,
+            out_dir: args.out_dir,
+            name: args.name,
+            template_dir: args.template_dir,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CliTemplateNewArgs {
+    /// Folder name for the new template (prompted when omitted)
+    pub id: Option<String>,
+
+    /// Registry directory containing the root template.yaml
+    #[arg(long = "template-dir")]
+    pub template_dir: Option<PathBuf>,
+}
+
+impl From<CliTemplateNewArgs> for TemplateNewArgs {
+    fn from(args: CliTemplateNewArgs) -> Self {
+        TemplateNewArgs {
+            template_dir: args.template_dir,
+            id: args.id,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct BuildZipsArgs {
+    /// Local directory containing templates to build zips from (for development use)
+    #[arg(long = "template-dir")]
+    pub template_dir: Option<PathBuf>,
+}
+
+/// The clap command for a product, with its name, description and version filled in
+pub fn command<C: ProductConfig>(config: &C, cli_version: &'static str) -> clap::Command {
+    let create_about = format!("Create a new {} project", config.display_name());
+    Args::command()
+        .name(config.binary_name())
+        .bin_name(config.binary_name())
+        .about(config.cli_description())
+        .version(cli_version)
+        .mut_subcommand("create", |create| create.about(create_about))
+}
+
+/// Parse the command line and run the selected command for a product
+///
+/// Installs the panic and Ctrl+C handlers that restore the terminal cursor and roll back
+/// a half-finished `create`.
+pub async fn main<C: ProductConfig>(config: C, cli_version: &'static str) -> Result<()> {
+    // Ensure terminal cursor is restored on panic
+    let default_panic = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        ui::show_cursor();
+        default_panic(info);
+    }));
+
+    // Handle Ctrl+C gracefully
+    #[cfg(feature = "tui")]
+    ctrlc::set_handler(interrupted).ok();
+    #[cfg(not(feature = "tui"))]
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            interrupted();
+        }
+    });
+
+    let matches = command(&config, cli_version).get_matches();
+    let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    run(&config, args, cli_version).await
+}
+
+/// Remove anything a half-finished create wrote, then exit
+fn interrupted() {
+    crate::project::staging::rollback_interrupted();
+    ui::show_cursor();
+    std::process::exit(130);
+}
+
+/// Run parsed arguments for a product
+pub async fn run<C: ProductConfig>(config: &C, args: Args, cli_version: &str) -> Result<()> {
+    // Handle subcommands
+    match args.command {
+        Some(Command::Create(create_args)) => {
+            // Run the create flow with the create args
+            let result = crate::commands::run(config, create_args.into(), cli_version).await;
+
+            // Ensure cursor is visible on normal exit
+            ui::show_cursor();
+
+            result
+        }
+        Some(Command::Add(AddCommand::Worker(add_args))) => {
+            let result = crate::commands::run_add_worker(config, add_args.into()).await;
+
+            // Ensure cursor is visible on normal exit
+            ui::show_cursor();
+
+            result
+        }
+        Some(Command::Upgrade(upgrade_args)) => {
+            let result =
+                crate::commands::run_upgrade(config, upgrade_args.into(), cli_version).await;
+
+            // Ensure cursor is visible on normal exit
+            ui::show_cursor();
+
+            result
+        }
+        Some(Command::Status(status_args)) => {
+            let result = crate::commands::run_status(config, status_args.into()).await;
+
+            // Ensure cursor is visible on normal exit
+            ui::show_cursor();
+
+            result
+        }
+        Some(Command::List(list_args)) => {
+            crate::templates::list_templates(config, &list_args.template_dir, list_args.json).await
+        }
+        Some(Command::Info(info_args)) => {
+            crate::templates::show_template_info(config, info_args.into()).await
+        }
+        Some(Command::Validate(validate_args)) => {
+            crate::templates::validate_templates(&validate_args.template_dir, validate_args.json)
+        }
+        Some(Command::Template(TemplateCommand::Extract(extract_args))) => {
+            crate::templates::run_extract(config, extract_args.into()).await
+        }
+        Some(Command::Template(TemplateCommand::New(new_args))) => {
+            let result = crate::commands::run_template_new(config, new_args.into()).await;
+
+            // Ensure cursor is visible on normal exit
+            ui::show_cursor();
+
+            result
+        }
+        Some(Command::BuildZips(build_args)) => {
+            // Build zip files for templates
+            crate::templates::build_zips(config, &build_args.template_dir).await
+        }
+        None => {
+            // No subcommand provided, default to create behavior (interactive mode)
+            let create_args = CreateArgs::default();
+            let result = crate::commands::run(config, create_args, cli_version).await;
+
+            // Ensure cursor is visible on normal exit
+            ui::show_cursor();
+
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::product::ProductDefinition;
+
+    fn product() -> ProductDefinition {
+        ProductDefinition::from_yaml(
+            "name: acme\ndisplay_name: Acme\nbinary_name: acme-tools\ndescription: Scaffold Acme projects\ntemplate_url: https://example.com/templates\ntemplate_url_env: ACME_TEMPLATE_URL\ndocs_url: https://example.com/docs\nupgrade_command: brew upgrade acme\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn command_is_branded_by_the_product() {
+        let command = command(&product(), "1.2.3");
+        command.clone().debug_assert();
+
+        assert_eq!(command.get_name(), "acme-tools");
+        assert_eq!(command.get_version(), Some("1.2.3"));
+        let create = command.find_subcommand("create").unwrap();
+        assert_eq!(
+            create.get_about().map(|a| a.to_string()),
+            Some("Create a new Acme project".to_string())
+        );
+    }
+
+    #[test]
+    fn arguments_parse_into_commands() {
+        let matches = command(&product(), "1.2.3")
+            .try_get_matches_from([
+                "acme-tools",
+                "create",
+                "-t",
+                "quickstart",
+                "-l",
+                "ts,py",
+                "-y",
+            ])
+            .unwrap();
+        let args = Args::from_arg_matches(&matches).unwrap();
+        match args.command {
+            Some(Command::Create(create)) => {
+                assert_eq!(create.template.as_deref(), Some("quickstart"));
+                assert_eq!(
+                    create.languages,
+                    Some(vec!["ts".to_string(), "py".to_string()])
+                );
+                assert!(create.yes);
+            }
+            other => panic!("expected create, got {:?}", other),
+        }
+    }
+}
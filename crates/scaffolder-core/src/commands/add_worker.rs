@@ -0,0 +1,184 @@
+//! `add worker` command: copy one worker folder from a template into an existing project
+
+use super::create::setup_fetcher;
+use crate::product::ProductConfig;
+use crate::project::{self, lock::ScaffoldLock};
+use crate::runtime::check::{self, Language};
+use crate::templates::{copier, worker};
+use crate::ui;
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// CLI arguments for the add worker command
+#[derive(Debug, Clone, Default)]
+pub struct AddWorkerArgs {
+    /// Local directory to use for templates instead of fetching from remote
+    pub template_dir: Option<PathBuf>,
+
+    /// Worker to copy, as `<template>/<worker-dir>`
+    pub from: String,
+
+    /// New worker name (defaults to the source worker directory name)
+    pub name: Option<String>,
+
+    /// Project directory to add the worker to (defaults to the current directory)
+    pub directory: Option<PathBuf>,
+
+    /// Template option assignments; options not given keep the values in `.iii/scaffold.lock`
+    pub options: Vec<String>,
+
+    /// Template variable assignments; variables not given keep the values in `.iii/scaffold.lock`
+    pub variables: Vec<String>,
+}
+
+/// Run the add worker command
+pub async fn run_add_worker<C: ProductConfig>(config: &C, args: AddWorkerArgs) -> Result<()> {
+    ui::intro(format!("{} add worker", config.display_name()))?;
+
+    let project_dir = project::resolve_project_dir(args.directory.as_deref());
+
+    if !project_dir.is_dir() {
+        anyhow::bail!("Project directory not found: {}", project_dir.display());
+    }
+    if !project_dir.join(".iii").join("project.ini").exists() {
+        ui::log::warning(format!(
+            "No .iii/project.ini in {}, adding the worker anyway",
+            project_dir.display()
+        ))?;
+    }
+
+    let (template_name, worker_dir) = worker::parse_worker_source(&args.from)?;
+    let worker_name = match &args.name {
+        Some(name) => name.clone(),
+        None => worker_dir
+            .rsplit('/')
+            .next()
+            .unwrap_or(&worker_dir)
+            .to_string(),
+    };
+    if worker_name.is_empty() || worker_name.contains(['/', '\\']) || worker_name.starts_with('.') {
+        anyhow::bail!("Invalid worker name: {}", worker_name);
+    }
+
+    let target_dir = project_dir.join(worker::WORKERS_DIR).join(&worker_name);
+    if target_dir.exists() {
+        anyhow::bail!("Worker directory already exists: {}", target_dir.display());
+    }
+
+    let mut fetcher = setup_fetcher(config, &args.template_dir)?;
+
+    let spinner = ui::spinner();
+    spinner.start("Loading template...");
+    let root_manifest = fetcher.fetch_root_manifest().await?;
+    let manifest = match fetcher.fetch_template_manifest(&template_name).await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            spinner.stop("Failed to load template");
+            return Err(e);
+        }
+    };
+    let narrowed = match worker::worker_manifest(&manifest, &worker_dir, &worker_name) {
+        Ok(narrowed) => narrowed,
+        Err(e) => {
+            spinner.stop("Worker not found");
+            return Err(e);
+        }
+    };
+    spinner.stop(format!(
+        "Template: {} - {}",
+        manifest.name, manifest.description
+    ));
+
+    let language_files = root_manifest.language_files_for(&manifest);
+
+    // The worker manifest tells us which runtime the worker needs
+    let worker_manifest_path = narrowed
+        .files
+        .iter()
+        .find(|f| f.destination().ends_with(worker::WORKER_MANIFEST))
+        .map(|f| f.path.clone())
+        .unwrap_or_default();
+    let worker_yaml = fetcher
+        .fetch_file(&template_name, &worker_manifest_path)
+        .await?;
+
+    match worker::worker_language(&worker_yaml) {
+        Some(language) => match check::check_runtimes(&[language]) {
+            Ok(runtimes) => {
+                let runtime_info: Vec<String> = runtimes
+                    .iter()
+                    .map(|r| format!("{} ({})", r.name, r.version.as_deref().unwrap_or("unknown")))
+                    .collect();
+                ui::log::success(format!(
+                    "{} worker, detected runtimes: {}",
+                    language.display_name(),
+                    runtime_info.join(", ")
+                ))?;
+            }
+            Err(e) => {
+                ui::log::error(format!("{}", e))?;
+                anyhow::bail!("Please install the missing runtimes and try again.");
+            }
+        },
+        None => {
+            ui::log::warning("Worker does not declare runtime.language, skipping runtime check")?;
+        }
+    }
+
+    // Copy the whole worker folder: every file in it belongs to the worker
+    let all_languages = [
+        Language::TypeScript,
+        Language::JavaScript,
+        Language::Python,
+        Language::Rust,
+    ];
+    // Option-gated worker files follow the options the project was created with
+    let lock = ScaffoldLock::read(&project_dir)
+        .await
+        .ok()
+        .filter(|lock| lock.template == template_name);
+    let options = worker::worker_options(
+        &manifest,
+        lock.as_ref().map(|lock| &lock.options),
+        &args.options,
+    )?;
+
+    let variables = worker::worker_variables(
+        &manifest,
+        lock.as_ref().map(|lock| &lock.variables),
+        &args.variables,
+    )?;
+
+    let mut files = copier::render_template(
+        &mut fetcher,
+        &template_name,
+        &narrowed,
+        &all_languages,
+        &options,
+        &language_files,
+    )
+    .await?;
+    copier::apply_variables(&mut files, &variables)?;
+    let copied_files = copier::write_files(&project_dir, &files).await?;
+
+    let worker_manifest_file = target_dir.join(worker::WORKER_MANIFEST);
+    let written = tokio::fs::read_to_string(&worker_manifest_file).await?;
+    tokio::fs::write(
+        &worker_manifest_file,
+        worker::rename_worker(&written, &worker_name),
+    )
+    .await?;
+
+    ui::log::success(format!(
+        "Added {} files to {}/{}",
+        copied_files.len(),
+        worker::WORKERS_DIR,
+        worker_name
+    ))?;
+    ui::outro(format!(
+        "Worker '{}' is ready. Install its dependencies before starting it.",
+        worker_name
+    ))?;
+
+    Ok(())
+}
@@ -0,0 +1,1120 @@
+//! `create` command: asks for whatever the flags leave open, then scaffolds the project
+
+use super::report::OutputFormat;
+use crate::builder::{self, BuildError, ProjectPlan, SelectedTemplate};
+use crate::config::UserConfig;
+use crate::events::{Event, EventSink};
+use crate::product::ProductConfig;
+use crate::project::answers::Answers;
+use crate::project::lock;
+use crate::prompter::{Confirmation, ConflictChoice, Prompter, ToolAction};
+use crate::runtime::check;
+use crate::telemetry::{self, DependencyInstall};
+use crate::templates::copier::{ConflictPolicy, ConflictResolution};
+use crate::templates::manifest::{
+    self, LanguageFiles, OptionValues, TemplateManifest, VariableValues,
+};
+use crate::templates::{copier, fetcher::TemplateFetcher, hooks, version};
+use crate::ui::{self, TerminalEvents, TerminalPrompter};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// CLI arguments for the create command
+#[derive(Debug, Clone, Default)]
+pub struct CreateArgs {
+    /// Local directory to use for templates instead of fetching from remote
+    pub template_dir: Option<PathBuf>,
+
+    /// Template name to use
+    pub template: Option<String>,
+
+    /// Project directory to create
+    pub directory: Option<PathBuf>,
+
+    /// Languages to include
+    pub languages: Option<Vec<String>>,
+
+    /// Template option assignments (`name=true`, `name=false` or bare `name`)
+    pub options: Vec<String>,
+
+    /// Template variable assignments (`name=value`)
+    pub variables: Vec<String>,
+
+    /// Skip tool installation check (e.g., iii)
+    pub skip_tool_check: bool,
+
+    /// Skip the template's post-create hooks
+    pub no_hooks: bool,
+
+    /// Run the template's post-create hooks without asking; `yes` alone does not run them
+    pub allow_hooks: bool,
+
+    /// How to handle files that already exist (defaults to prompt, or overwrite with --yes)
+    pub on_conflict: Option<ConflictPolicy>,
+
+    /// Show the files that would be written without creating anything
+    pub dry_run: bool,
+
+    /// Auto-confirm all prompts (non-interactive mode)
+    pub yes: bool,
+
+    /// Answers file supplying template, directory, languages and options; flags take precedence
+    pub answers: Option<PathBuf>,
+
+    /// Write the resolved choices to this answers file
+    pub save_answers: Option<PathBuf>,
+
+    /// `Json` prints a `CreateReport` on stdout at the end and keeps all other output on stderr
+    pub output: OutputFormat,
+}
+
+/// What `create_project` wrote, for the final report
+struct CreatedProject {
+    files: Vec<String>,
+    dependency_installs: Vec<DependencyInstall>,
+    project_id: String,
+}
+
+/// Run the CLI with interactive prompts
+pub async fn run<C: ProductConfig>(
+    config: &C,
+    mut args: CreateArgs,
+    cli_version: &str,
+) -> Result<()> {
+    ui::intro(config.display_name())?;
+
+    if let Some(path) = &args.answers {
+        let answers = Answers::read(path).await?;
+        ui::log::info(format!("Using answers from {}", path.display()))?;
+        apply_answers(&mut args, answers);
+    }
+
+    let user_config = UserConfig::load(config)?;
+    for source in user_config.sources.iter().rev() {
+        ui::log::info(format!("Using settings from {}", source.display()))?;
+    }
+    args.on_conflict = args.on_conflict.or(user_config.on_conflict);
+
+    let mut prompter = TerminalPrompter::new(args.output);
+    let events = TerminalEvents::new();
+    run_with(
+        config,
+        args,
+        cli_version,
+        &user_config,
+        &mut prompter,
+        &events,
+    )
+    .await
+}
+
+/// The `create` flow after setup, taking every choice the flags leave open from `prompter`
+/// and reporting progress to `events`
+async fn run_with<C: ProductConfig, P: Prompter>(
+    config: &C,
+    args: CreateArgs,
+    cli_version: &str,
+    user_config: &UserConfig,
+    prompter: &mut P,
+    events: &dyn EventSink,
+) -> Result<()> {
+    // Step 1: Check tool installation (skip if --skip-tool-check or product doesn't require it)
+    if args.dry_run {
+        ui::log::info("Dry run: nothing will be written")?;
+    } else if config.requires_iii() && !args.skip_tool_check {
+        handle_tool_check(config, &args, prompter, events).await?;
+    } else if args.skip_tool_check {
+        ui::log::info("Skipping tool check")?;
+    }
+
+    // Step 2: Setup template fetcher
+    let mut fetcher = setup_fetcher(config, &args.template_dir)?;
+
+    // Step 3: Select template (also returns merged language_files)
+    let (template_name, manifest, language_files) =
+        select_template(&mut fetcher, args.template.as_deref(), prompter, events).await?;
+
+    // Check version compatibility (CLI tools version — advisory)
+    if let Some(warning) =
+        version::check_compatibility(cli_version, &manifest.version, config.upgrade_command())
+    {
+        ui::log::warning(format!(
+            "Version warning: {}",
+            warning.lines().next().unwrap_or(&warning)
+        ))?;
+    }
+
+    // Check iii engine version compatibility (hard block, respects --skip-tool-check)
+    if !args.skip_tool_check {
+        match builder::check_engine(&manifest) {
+            Ok(Some((installed, min_ver))) => {
+                ui::log::success(format!(
+                    "iii engine {} (>= {} required)",
+                    installed, min_ver
+                ))?;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                ui::log::error(e.to_string())?;
+                return Err(e.into());
+            }
+        }
+    }
+
+    // Step 4: Select directory
+    let project_dir = select_directory(&args, prompter)?;
+
+    // Step 5: Select languages
+    let selected_languages = select_languages(
+        &manifest,
+        &args,
+        &user_config.preferred_languages(),
+        prompter,
+    )?;
+
+    // Step 6: Select template options and variables
+    let options = select_options(&manifest, &args, prompter)?;
+    let variables = select_variables(&manifest, &args, prompter)?;
+
+    if let Some(path) = &args.save_answers {
+        let answers = Answers {
+            template: Some(template_name.clone()),
+            directory: Some(answer_directory(&project_dir)),
+            languages: Some(lock::language_names(&selected_languages)),
+            options: options.clone(),
+            variables: variables.clone(),
+            allow_hooks: args.allow_hooks,
+        };
+        answers.write(path).await?;
+        ui::log::success(format!("Saved answers to {}", path.display()))?;
+    }
+
+    // Step 7: Check runtimes (advisory = included languages that don't cause hard fail)
+    let runtimes = check_runtimes(&manifest, &selected_languages, events)?;
+
+    // Step 8: Create project
+    let plan = ProjectPlan {
+        template_name: &template_name,
+        manifest: &manifest,
+        language_files: &language_files,
+        project_dir: &project_dir,
+        selected_languages: &selected_languages,
+        options: &options,
+        variables: &variables,
+    };
+
+    let mut report = plan.report(runtimes, args.dry_run);
+
+    if args.dry_run {
+        report.files = print_dry_run(&mut fetcher, &plan, args.output, events).await?;
+    } else {
+        let created = create_project(
+            cli_version,
+            &mut fetcher,
+            &plan,
+            &args,
+            user_config,
+            prompter,
+            events,
+        )
+        .await?;
+
+        if user_config.telemetry_enabled() {
+            builder::send_project_created(
+                config.name(),
+                cli_version,
+                &plan,
+                &created.project_id,
+                events,
+            )
+            .await;
+        }
+
+        report.files = created.files;
+        report.dependency_installs = created.dependency_installs;
+        report.project_id = Some(created.project_id);
+
+        // Step 9: Show next steps
+        print_next_steps(&report.next_steps, args.output)?;
+    }
+
+    if args.output.is_json() {
+        report.print()?;
+    }
+
+    Ok(())
+}
+
+/// Fill in whatever the command line left unset from an answers file
+fn apply_answers(args: &mut CreateArgs, answers: Answers) {
+    let option_assignments = answers.option_assignments();
+    let variable_assignments = answers.variable_assignments();
+    args.template = args.template.take().or(answers.template);
+    args.directory = args.directory.take().or(answers.directory);
+    args.languages = args.languages.take().or(answers.languages);
+    // Later assignments win, so `--option` and `--var` flags override the file
+    args.options = option_assignments
+        .into_iter()
+        .chain(std::mem::take(&mut args.options))
+        .collect();
+    args.variables = variable_assignments
+        .into_iter()
+        .chain(std::mem::take(&mut args.variables))
+        .collect();
+    args.allow_hooks |= answers.allow_hooks;
+}
+
+/// Project directory as an answers file records it: relative to the working directory when inside it
+fn answer_directory(project_dir: &Path) -> PathBuf {
+    let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    match project_dir.strip_prefix(&current_dir) {
+        Ok(relative) if relative.as_os_str().is_empty() => PathBuf::from("."),
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => project_dir.to_path_buf(),
+    }
+}
+
+async fn handle_tool_check<C: ProductConfig>(
+    _config: &C,
+    args: &CreateArgs,
+    prompter: &mut impl Prompter,
+    events: &dyn EventSink,
+) -> Result<()> {
+    // Create tool manager for iii
+    let tool = crate::runtime::tool::iii_tool();
+
+    let installed = tool.is_installed();
+
+    if installed {
+        let version = tool.get_version().unwrap_or_else(|| "unknown".to_string());
+        ui::log::success(format!(
+            "{} installed ({})",
+            tool.config().display_name,
+            version
+        ))?;
+        return Ok(());
+    }
+
+    ui::log::warning(format!("{} is not installed", tool.config().display_name))?;
+
+    // In non-interactive mode, just skip
+    if args.yes {
+        ui::log::info(format!(
+            "Continuing without {} (--yes mode)",
+            tool.config().display_name
+        ))?;
+        return Ok(());
+    }
+
+    let action = prompter.tool_action(tool.config(), &tool.install_command())?;
+
+    match action {
+        ToolAction::Install => {
+            let command = tool.install_command();
+            ui::log::info(format!("This will execute: {}", command))?;
+
+            let confirm = prompter.confirm(Confirmation::InstallTool {
+                tool: tool.config().display_name,
+                command: &command,
+            })?;
+
+            if confirm {
+                // Success and failure are reported through `events`
+                if tool.install(events).await.is_err() {
+                    let continue_anyway = prompter.confirm(Confirmation::ContinueWithoutTool {
+                        tool: tool.config().display_name,
+                    })?;
+
+                    if !continue_anyway {
+                        anyhow::bail!("Setup cancelled.");
+                    }
+                }
+            } else {
+                ui::log::info(format!(
+                    "Continuing without {}. Refer to the docs for installation instructions: ({})",
+                    tool.config().display_name,
+                    tool.config().docs_url
+                ))?;
+            }
+        }
+        ToolAction::OpenDocs => {
+            tool.open_docs()?;
+            ui::outro(format!(
+                "After installing {}, run this command again.",
+                tool.config().display_name
+            ))?;
+            std::process::exit(0);
+        }
+        ToolAction::Skip => {
+            ui::log::info(format!(
+                "Continuing without {}. Refer to the docs for installation instructions: ({})",
+                tool.config().display_name,
+                tool.config().docs_url
+            ))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn setup_fetcher<C: ProductConfig>(
+    config: &C,
+    template_dir: &Option<PathBuf>,
+) -> Result<TemplateFetcher> {
+    let fetcher = match template_dir {
+        Some(path) => {
+            ui::log::info(format!("Using local templates from {}", path.display()))?;
+            TemplateFetcher::from_local(path.clone(), config.user_agent())
+        }
+        None => {
+            ui::log::info("Using remote templates")?;
+            TemplateFetcher::from_config(config)?
+        }
+    };
+
+    Ok(fetcher)
+}
+
+async fn select_template(
+    fetcher: &mut TemplateFetcher,
+    specified_template: Option<&str>,
+    prompter: &mut impl Prompter,
+    events: &dyn EventSink,
+) -> Result<(String, TemplateManifest, LanguageFiles)> {
+    let spinner = ui::spinner();
+    spinner.start("Loading templates...");
+
+    let root_manifest = fetcher.fetch_root_manifest().await?;
+
+    // If a template was specified via --template flag, use it directly
+    if let Some(template_name) = specified_template {
+        let SelectedTemplate {
+            name,
+            manifest,
+            language_files,
+        } = match builder::fetch_template(fetcher, &root_manifest, template_name, events).await {
+            Ok(template) => template,
+            Err(e) => {
+                spinner.stop("Failed to load templates");
+                return Err(e.into());
+            }
+        };
+        spinner.stop(format!(
+            "Template: {} - {}",
+            manifest.name, manifest.description
+        ));
+        return Ok((name, manifest, language_files));
+    }
+
+    let mut templates: Vec<(String, TemplateManifest)> = Vec::new();
+    for template_name in &root_manifest.templates {
+        let manifest = fetcher.fetch_template_manifest(template_name).await?;
+        templates.push((template_name.clone(), manifest));
+    }
+
+    spinner.stop("Templates loaded");
+
+    if templates.is_empty() {
+        anyhow::bail!("No templates found.");
+    }
+
+    // If only one template, use it automatically
+    let (name, manifest) = if templates.len() == 1 {
+        let (name, manifest) = templates.into_iter().next().unwrap();
+        ui::log::info(format!(
+            "Using template: {} - {}",
+            manifest.name, manifest.description
+        ))?;
+        (name, manifest)
+    } else {
+        let selected_idx = prompter.select_template(&templates)?;
+        let Some(selected) = templates.into_iter().nth(selected_idx) else {
+            anyhow::bail!("No template at index {}", selected_idx);
+        };
+        selected
+    };
+
+    let language_files = root_manifest.language_files_for(&manifest);
+    events.emit(Event::TemplateFetched {
+        template: name.clone(),
+        version: manifest.version.clone(),
+    });
+
+    Ok((name, manifest, language_files))
+}
+
+fn select_directory(args: &CreateArgs, prompter: &mut impl Prompter) -> Result<PathBuf> {
+    // Use --directory flag if provided
+    let path = if let Some(dir) = &args.directory {
+        let p = builder::resolve_directory(dir)?;
+        ui::log::info(format!("Using directory: {}", p.display()))?;
+        p
+    } else {
+        let input = prompter.project_directory()?;
+        builder::resolve_directory(Path::new(&input))?
+    };
+
+    // Warn if directory exists and has files
+    if path.exists() && path.is_dir() {
+        if let Ok(entries) = std::fs::read_dir(&path) {
+            let count = entries.count();
+            if count > 0 {
+                ui::log::warning(format!("Directory has {} existing items", count))?;
+
+                // Auto-confirm with --yes flag; dry runs report overwrites instead
+                let confirm = args.yes
+                    || args.dry_run
+                    || prompter.confirm(Confirmation::NonEmptyDirectory {
+                        path: &path,
+                        entries: count,
+                    })?;
+
+                if !confirm {
+                    anyhow::bail!("Setup cancelled.");
+                }
+            }
+        }
+    }
+
+    Ok(path)
+}
+
+/// `preferred` (from the user config) replaces "all optional languages" under `--yes`
+/// and is preselected in the prompt
+fn select_languages(
+    manifest: &TemplateManifest,
+    args: &CreateArgs,
+    preferred: &[check::Language],
+    prompter: &mut impl Prompter,
+) -> Result<Vec<check::Language>> {
+    let mut required_languages: Vec<check::Language> = Vec::new();
+    let mut included_languages: Vec<check::Language> = Vec::new();
+    let mut optional_languages: Vec<check::Language> = Vec::new();
+
+    let treat_as_included = manifest.treat_required_as_included;
+
+    for (lang_str, lang) in [
+        ("typescript", check::Language::TypeScript),
+        ("javascript", check::Language::JavaScript),
+        ("python", check::Language::Python),
+        ("rust", check::Language::Rust),
+    ] {
+        if manifest.is_required(lang_str) {
+            if treat_as_included {
+                included_languages.push(lang);
+            } else {
+                required_languages.push(lang);
+            }
+        } else if manifest.is_optional(lang_str) {
+            optional_languages.push(lang);
+        }
+    }
+
+    if !required_languages.is_empty() {
+        let names: Vec<&str> = required_languages
+            .iter()
+            .map(|l| l.display_name())
+            .collect();
+        ui::log::info(format!("Required: {}", names.join(", ")))?;
+    }
+
+    let mut selected_languages = required_languages.clone();
+    selected_languages.extend(included_languages.iter().copied());
+
+    let selectable: Vec<check::Language> = optional_languages.clone();
+
+    if let Some(lang_args) = &args.languages {
+        for lang_str in lang_args {
+            if let Some(lang) = check::Language::parse(lang_str) {
+                if selectable.contains(&lang) && !selected_languages.contains(&lang) {
+                    selected_languages.push(lang);
+                }
+            } else {
+                ui::log::warning(format!("Unknown language: {}", lang_str))?;
+            }
+        }
+    } else if !selectable.is_empty() {
+        let preselected: Vec<check::Language> = selectable
+            .iter()
+            .filter(|l| preferred.contains(l))
+            .copied()
+            .collect();
+        if args.yes && !preferred.is_empty() {
+            let to_add: Vec<_> = preselected
+                .iter()
+                .filter(|l| !selected_languages.contains(l))
+                .copied()
+                .collect();
+            selected_languages.extend(to_add);
+        } else if args.yes {
+            let to_add: Vec<_> = selectable
+                .iter()
+                .filter(|l| !selected_languages.contains(l))
+                .copied()
+                .collect();
+            if !to_add.is_empty() {
+                let names: Vec<&str> = to_add.iter().map(|l| l.display_name()).collect();
+                ui::log::info(format!(
+                    "Including all optional languages (--yes): {}. Pass --languages or --answers to choose",
+                    names.join(", ")
+                ))?;
+            }
+            selected_languages.extend(to_add);
+        } else {
+            let prompt = if included_languages.is_empty() && optional_languages.is_empty() {
+                "Select languages"
+            } else {
+                "Select additional languages (optional)"
+            };
+            let selected = prompter.select_languages(prompt, &selectable, &preselected)?;
+
+            selected_languages = required_languages.clone();
+            selected_languages.extend(included_languages.iter().copied());
+            for lang in selected {
+                if !selected_languages.contains(&lang) {
+                    selected_languages.push(lang);
+                }
+            }
+        }
+    }
+
+    if selected_languages.is_empty() {
+        anyhow::bail!("No languages available for this template.");
+    }
+
+    let included_set: std::collections::HashSet<_> = included_languages.iter().collect();
+    let selected_set: std::collections::HashSet<_> = selected_languages.iter().collect();
+    if !included_languages.is_empty() && included_set != selected_set {
+        let names: Vec<&str> = included_languages
+            .iter()
+            .map(|l| l.display_name())
+            .collect();
+        ui::log::info(format!("Included: {}", names.join(", ")))?;
+    }
+
+    let lang_names: Vec<&str> = selected_languages
+        .iter()
+        .map(|l| l.display_name())
+        .collect();
+    ui::log::success(format!("Project languages: {}", lang_names.join(", ")))?;
+
+    Ok(selected_languages)
+}
+
+fn select_options(
+    manifest: &TemplateManifest,
+    args: &CreateArgs,
+    prompter: &mut impl Prompter,
+) -> Result<OptionValues> {
+    let mut answers = OptionValues::new();
+    for assignment in &args.options {
+        let (name, value) = manifest::parse_option_assignment(assignment)?;
+        answers.insert(name, value);
+    }
+
+    // Validates names and fills in defaults for anything not answered
+    let mut options = manifest.resolve_options(&answers)?;
+
+    let unanswered: Vec<_> = manifest
+        .options
+        .iter()
+        .filter(|o| !answers.contains_key(&o.name))
+        .collect();
+
+    if !unanswered.is_empty() && !args.yes {
+        let selected = prompter.select_options(&unanswered)?;
+
+        for option in &unanswered {
+            options.insert(option.name.clone(), selected.contains(&option.name));
+        }
+    }
+
+    let enabled: Vec<&str> = options
+        .iter()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !enabled.is_empty() {
+        ui::log::success(format!("Template options: {}", enabled.join(", ")))?;
+    }
+
+    Ok(options)
+}
+
+fn select_variables(
+    manifest: &TemplateManifest,
+    args: &CreateArgs,
+    prompter: &mut impl Prompter,
+) -> Result<VariableValues> {
+    let mut answers = VariableValues::new();
+    for assignment in &args.variables {
+        let (name, value) = manifest::parse_variable_assignment(assignment)?;
+        if manifest.variable(&name).is_none() {
+            anyhow::bail!("Unknown variable '{}'", name);
+        }
+        answers.insert(name, value);
+    }
+
+    // With --yes, unanswered variables take their defaults
+    if !args.yes {
+        for variable in &manifest.variables {
+            if !answers.contains_key(&variable.name) {
+                let value = prompter.input_variable(variable)?;
+                answers.insert(variable.name.clone(), value);
+            }
+        }
+    }
+
+    // Fills in defaults and rejects variables still without a value
+    manifest.resolve_variables(&answers)
+}
+
+fn check_runtimes(
+    manifest: &TemplateManifest,
+    selected_languages: &[check::Language],
+    events: &dyn EventSink,
+) -> Result<Vec<check::RuntimeInfo>> {
+    match builder::required_runtimes(manifest, selected_languages, events) {
+        Ok(runtimes) => Ok(runtimes),
+        // The missing runtimes were already shown through `events`
+        Err(BuildError::MissingRuntimes(_)) => {
+            anyhow::bail!("Please install the missing runtimes and try again.")
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn create_project(
+    cli_version: &str,
+    fetcher: &mut TemplateFetcher,
+    plan: &ProjectPlan<'_>,
+    args: &CreateArgs,
+    user_config: &UserConfig,
+    prompter: &mut impl Prompter,
+    events: &dyn EventSink,
+) -> Result<CreatedProject> {
+    let rendered = plan.render(fetcher, events).await?;
+
+    let resolved = resolve_project_writes(plan.project_dir, &rendered, args, prompter).await?;
+
+    // Everything is written to a staging directory first and moved into place at once;
+    // any failure from here on removes exactly the files this run created
+    let written = builder::write_project(
+        plan,
+        &resolved,
+        &fetcher.source().redacted(),
+        cli_version,
+        events,
+        || async {
+            run_post_create_hooks(plan, args, prompter, events).await?;
+
+            let package_manager = user_config.package_manager.unwrap_or_default();
+            Ok(telemetry::run_dependency_install(
+                plan.project_dir,
+                plan.selected_languages,
+                package_manager,
+                events,
+            )
+            .await?)
+        },
+    )
+    .await;
+
+    let written = match written {
+        Ok(written) => written,
+        // The rollback was already reported through `events`
+        Err(BuildError::RolledBack { source, .. }) => return Err((*source).into()),
+        Err(e) => return Err(e.into()),
+    };
+    let dependency_installs = written.finished;
+
+    // Merges change files the user already had, so summarise them last where they stay visible
+    builder::report_merges(&resolved, events);
+
+    Ok(CreatedProject {
+        files: resolved.files.iter().map(|f| f.dest.clone()).collect(),
+        dependency_installs,
+        project_id: written.project_id,
+    })
+}
+
+/// Decide how to handle existing files according to `--on-conflict`
+async fn resolve_project_writes(
+    project_dir: &Path,
+    rendered: &[copier::RenderedFile],
+    args: &CreateArgs,
+    prompter: &mut impl Prompter,
+) -> Result<copier::ResolvedWrites> {
+    let policy = args.on_conflict.unwrap_or(if args.yes {
+        ConflictPolicy::Overwrite
+    } else {
+        ConflictPolicy::Prompt
+    });
+    // `--yes` overwrites by default but still merges well-known files; only an explicit
+    // `--on-conflict overwrite` replaces them wholesale
+    let merge_known = args.on_conflict != Some(ConflictPolicy::Overwrite);
+
+    match policy {
+        ConflictPolicy::Prompt => {
+            let mut apply_to_all: Option<ConflictResolution> = None;
+            copier::resolve_writes(project_dir, rendered, merge_known, |path, existing, new| {
+                if let Some(resolution) = apply_to_all {
+                    return Ok(resolution);
+                }
+                let ConflictChoice {
+                    resolution,
+                    apply_to_all: all,
+                } = prompter.resolve_conflict(path, existing, new)?;
+                if all {
+                    apply_to_all = Some(resolution);
+                }
+                Ok(resolution)
+            })
+            .await
+        }
+        policy => match builder::resolve_writes(project_dir, rendered, policy, merge_known).await {
+            Err(e @ BuildError::Conflicts(_)) => {
+                anyhow::bail!("{}. Use --on-conflict to choose how to handle them.", e)
+            }
+            resolved => Ok(resolved?),
+        },
+    }
+}
+
+/// Show what `create` would write and return the planned file paths
+async fn print_dry_run(
+    fetcher: &mut TemplateFetcher,
+    plan: &ProjectPlan<'_>,
+    output: OutputFormat,
+    events: &dyn EventSink,
+) -> Result<Vec<String>> {
+    let rendered = plan.render(fetcher, events).await?;
+
+    let planned = copier::plan_writes(plan.project_dir, &rendered);
+    let overwrites = planned.iter().filter(|p| p.overwrites).count();
+    let total_size: usize = planned.iter().map(|p| p.size).sum();
+
+    output.println("");
+    output.println(format!("  {}", plan.project_dir.display()));
+    for line in copier::file_tree_lines(&planned) {
+        output.println(format!("    {}", line));
+    }
+    output.println("");
+
+    let hooks = hooks::applicable_hooks(plan.manifest, plan.selected_languages, plan.options);
+    for hook in hooks {
+        ui::log::info(format!("Would run post-create hook: {}", hook.run))?;
+    }
+
+    ui::outro(format!(
+        "Dry run: {} files ({} bytes), {} would overwrite existing files, plus .iii/ project metadata",
+        planned.len(),
+        total_size,
+        overwrites
+    ))?;
+
+    Ok(planned.into_iter().map(|p| p.dest).collect())
+}
+
+async fn run_post_create_hooks(
+    plan: &ProjectPlan<'_>,
+    args: &CreateArgs,
+    prompter: &mut impl Prompter,
+    events: &dyn EventSink,
+) -> Result<()> {
+    let applicable = hooks::applicable_hooks(plan.manifest, plan.selected_languages, plan.options);
+    if applicable.is_empty() {
+        return Ok(());
+    }
+
+    if args.no_hooks {
+        ui::log::info(format!(
+            "Skipping {} post-create hook(s) (--no-hooks)",
+            applicable.len()
+        ))?;
+        return Ok(());
+    }
+
+    let listing: Vec<String> = applicable
+        .iter()
+        .map(|hook| {
+            let location = hook.cwd.as_deref().unwrap_or(".");
+            match &hook.description {
+                Some(description) => format!("{} ({}): {}", description, location, hook.run),
+                None => format!("({}): {}", location, hook.run),
+            }
+        })
+        .collect();
+    ui::log::info(format!(
+        "This template runs setup commands:\n{}",
+        listing.join("\n")
+    ))?;
+
+    // Hooks run commands from the template, so they need their own opt-in: `--yes`
+    // accepts the defaults elsewhere but never grants this
+    let consent = if args.allow_hooks {
+        true
+    } else if args.yes {
+        ui::log::info("Pass --allow-hooks to run them without asking")?;
+        false
+    } else {
+        prompter.confirm(Confirmation::RunHooks {
+            count: applicable.len(),
+        })?
+    };
+
+    if !consent {
+        ui::log::warning(
+            "Skipped post-create hooks. Run the commands above manually to finish setup.",
+        )?;
+        return Ok(());
+    }
+
+    // Each hook's outcome is reported through `events`; only required failures stop here
+    for hook in applicable {
+        if hooks::run_hook(plan.project_dir, hook, events)
+            .await
+            .is_err()
+            && hook.required
+        {
+            anyhow::bail!("Required post-create hook failed: {}", hook.run);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_next_steps(steps: &[String], output: OutputFormat) -> Result<()> {
+    if !steps.is_empty() {
+        output.println("");
+        output.println("  Next steps");
+        output.println("");
+
+        for (i, step) in steps.iter().enumerate() {
+            output.println(format!("  {}.  {}", i + 1, step));
+        }
+    }
+
+    ui::outro("Happy coding!")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::tool::ToolConfig;
+    use crate::templates::manifest::{TemplateOption, TemplateVariable};
+    use crate::ProductDefinition;
+
+    /// Answers from fixed values and records each question asked
+    #[derive(Default)]
+    struct ScriptedPrompter {
+        template: usize,
+        directory: String,
+        languages: Vec<check::Language>,
+        options: Vec<String>,
+        conflict: Option<ConflictResolution>,
+        asked: Vec<String>,
+    }
+
+    impl Prompter for ScriptedPrompter {
+        fn tool_action(&mut self, tool: &ToolConfig, _install_command: &str) -> Result<ToolAction> {
+            self.asked.push(format!("tool {}", tool.name));
+            Ok(ToolAction::Skip)
+        }
+
+        fn confirm(&mut self, confirmation: Confirmation<'_>) -> Result<bool> {
+            self.asked
+                .push(format!("confirm {}", confirmation.message()));
+            Ok(true)
+        }
+
+        fn select_template(&mut self, templates: &[(String, TemplateManifest)]) -> Result<usize> {
+            let names: Vec<&str> = templates.iter().map(|(name, _)| name.as_str()).collect();
+            self.asked.push(format!("template {}", names.join(",")));
+            Ok(self.template)
+        }
+
+        fn project_directory(&mut self) -> Result<String> {
+            self.asked.push("directory".to_string());
+            Ok(self.directory.clone())
+        }
+
+        fn select_languages(
+            &mut self,
+            _prompt: &str,
+            selectable: &[check::Language],
+            _preselected: &[check::Language],
+        ) -> Result<Vec<check::Language>> {
+            self.asked.push(format!("languages {:?}", selectable));
+            Ok(self.languages.clone())
+        }
+
+        fn select_options(&mut self, options: &[&TemplateOption]) -> Result<Vec<String>> {
+            let names: Vec<&str> = options.iter().map(|o| o.name.as_str()).collect();
+            self.asked.push(format!("options {}", names.join(",")));
+            Ok(self.options.clone())
+        }
+
+        fn input_variable(&mut self, variable: &TemplateVariable) -> Result<String> {
+            self.asked.push(format!("variable {}", variable.name));
+            Ok(format!("my-{}", variable.name))
+        }
+
+        fn resolve_conflict(
+            &mut self,
+            path: &str,
+            _existing: &[u8],
+            _new: &[u8],
+        ) -> Result<ConflictChoice> {
+            self.asked.push(format!("conflict {}", path));
+            Ok(ConflictChoice {
+                resolution: self.conflict.unwrap_or(ConflictResolution::Overwrite),
+                apply_to_all: false,
+            })
+        }
+    }
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let full = dir.join(path);
+        std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+        std::fs::write(full, content).unwrap();
+    }
+
+    /// Two templates; `api` has an optional language, an option, a variable and a hook. TypeScript is
+    /// included (advisory) so the runtime check passes without Node.js
+    fn registry() -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        write(
+            dir,
+            "template.yaml",
+            "templates: [docs, api]\nlanguage_files:\n  common: [README.md]\n  typescript: ['*.ts']\n",
+        );
+        write(
+            dir,
+            "docs/template.yaml",
+            "name: Docs\ndescription: Docs site\nversion: '0.1.0'\nrequires: [typescript]\ntreat_required_as_included: true\nfiles:\n  - README.md\n",
+        );
+        write(dir, "docs/README.md", "# Docs\n");
+        write(
+            dir,
+            "api/template.yaml",
+            "name: API\ndescription: API service\nversion: '0.1.0'\nrequires: [typescript]\noptional: [rust]\ntreat_required_as_included: true\nfiles:\n  - README.md\n  - src/index.ts\n  - Dockerfile\noptions:\n  - name: docker\n    default: true\n    files: [Dockerfile]\nvariables:\n  - name: service\npost_create:\n  - run: touch hooked\n",
+        );
+        write(dir, "api/README.md", "# API\n");
+        write(
+            dir,
+            "api/src/index.ts",
+            "export const service = '{{ service }}'\n",
+        );
+        write(dir, "api/Dockerfile", "FROM node\n");
+        tmp
+    }
+
+    #[tokio::test]
+    async fn interactive_flow_runs_without_a_tty() {
+        let registry = registry();
+        let out = tempfile::tempdir().unwrap();
+        write(out.path(), "README.md", "mine\n");
+        let product = ProductDefinition::from_yaml(
+            "name: acme\ndisplay_name: Acme\ndescription: Acme scaffolder\ntemplate_url: https://templates.example.com/\ntemplate_url_env: ACME_TEMPLATE_URL\ndocs_url: https://example.com/docs\nupgrade_command: cargo install acme\n",
+        )
+        .unwrap();
+        let args = CreateArgs {
+            template_dir: Some(registry.path().to_path_buf()),
+            ..Default::default()
+        };
+        let user_config = UserConfig {
+            telemetry: Some(false),
+            ..Default::default()
+        };
+        let mut prompter = ScriptedPrompter {
+            template: 1,
+            directory: out.path().display().to_string(),
+            conflict: Some(ConflictResolution::Skip),
+            ..Default::default()
+        };
+
+        let events = std::sync::Mutex::new(Vec::new());
+        let sink = |event: Event| events.lock().unwrap().push(event);
+
+        run_with(&product, args, "0.1.0", &user_config, &mut prompter, &sink)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            prompter.asked,
+            vec![
+                "template docs,api",
+                "directory",
+                "confirm Continue anyway?",
+                "languages [Rust]",
+                "options docker",
+                "variable service",
+                "conflict README.md",
+                "confirm Run these commands now?",
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(out.path().join("README.md")).unwrap(),
+            "mine\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(out.path().join("src/index.ts")).unwrap(),
+            "export const service = 'my-service'\n"
+        );
+        assert!(!out.path().join("Dockerfile").exists());
+        assert!(out.path().join("hooked").is_file());
+
+        let events = events.into_inner().unwrap();
+        assert!(events.contains(&Event::TemplateFetched {
+            template: "api".to_string(),
+            version: "0.1.0".to_string(),
+        }));
+        assert!(events.contains(&Event::FileSkipped {
+            path: "README.md".to_string(),
+        }));
+        assert!(events.contains(&Event::HookFinished {
+            command: "touch hooked".to_string(),
+            required: false,
+            error: None,
+        }));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, Event::ProjectWritten { files: 1, .. })));
+    }
+
+    #[test]
+    fn flags_take_precedence_over_answers() {
+        let mut args = CreateArgs {
+            template: Some("starter".to_string()),
+            options: vec!["docker=true".to_string()],
+            ..Default::default()
+        };
+        let answers = Answers {
+            template: Some("quickstart".to_string()),
+            directory: Some(PathBuf::from("app")),
+            languages: Some(Vec::new()),
+            options: OptionValues::from([
+                ("docker".to_string(), false),
+                ("redis".to_string(), true),
+            ]),
+            variables: VariableValues::from([("service".to_string(), "billing".to_string())]),
+            allow_hooks: true,
+        };
+
+        apply_answers(&mut args, answers);
+
+        assert_eq!(args.template.as_deref(), Some("starter"));
+        assert_eq!(args.directory, Some(PathBuf::from("app")));
+        assert_eq!(args.languages, Some(Vec::new()));
+        assert_eq!(
+            args.options,
+            vec!["docker=false", "redis=true", "docker=true"]
+        );
+        assert_eq!(args.variables, vec!["service=billing"]);
+        assert!(args.allow_hooks);
+    }
+}
@@ -0,0 +1,18 @@
+//! The commands behind the CLI
+//!
+//! Prompts and progress go through [`crate::ui`], so these run with cliclack prompts or
+//! plain-text ones depending on the `tui` feature.
+
+mod add_worker;
+mod create;
+mod report;
+mod status;
+mod template_new;
+mod upgrade;
+
+pub use add_worker::{run_add_worker, AddWorkerArgs};
+pub use create::{run, CreateArgs};
+pub use report::OutputFormat;
+pub use status::{run_status, StatusArgs};
+pub use template_new::{run_template_new, TemplateNewArgs};
+pub use upgrade::{run_upgrade, UpgradeArgs};
@@ -0,0 +1,27 @@
+//! Output format for commands that can report machine-readable results
+
+use std::fmt::Display;
+
+/// How a command reports its result
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable output
+    #[default]
+    Text,
+    /// A JSON report on stdout; everything else goes to stderr
+    Json,
+}
+
+impl OutputFormat {
+    pub fn is_json(self) -> bool {
+        self == OutputFormat::Json
+    }
+
+    /// Print a human-readable line, keeping stdout clear when it carries the JSON report
+    pub fn println(self, line: impl Display) {
+        match self {
+            OutputFormat::Text => println!("{}", line),
+            OutputFormat::Json => eprintln!("{}", line),
+        }
+    }
+}
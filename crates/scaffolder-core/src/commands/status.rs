@@ -0,0 +1,76 @@
+//! `status` command: compare generated files with the scaffold lockfile
+
+use crate::product::ProductConfig;
+use crate::project::lock::{FileState, ScaffoldLock};
+use crate::project::{self, ProjectInfo};
+use crate::ui;
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// CLI arguments for the status command
+#[derive(Debug, Clone, Default)]
+pub struct StatusArgs {
+    /// Project directory to inspect (defaults to the current directory)
+    pub directory: Option<PathBuf>,
+}
+
+/// Run the status command
+pub async fn run_status<C: ProductConfig>(config: &C, args: StatusArgs) -> Result<()> {
+    ui::intro(format!("{} status", config.display_name()))?;
+
+    let project_dir = project::resolve_project_dir(args.directory.as_deref());
+    let lock = ScaffoldLock::read(&project_dir).await?;
+
+    if let Ok(info) = ProjectInfo::read(&project_dir).await {
+        ui::log::info(format!(
+            "Project: {} ({})",
+            info.project_name, info.project_id
+        ))?;
+    }
+    ui::log::info(format!(
+        "Template: {} {} from {}",
+        lock.template, lock.template_version, lock.registry
+    ))?;
+    ui::log::info(format!(
+        "Generated by CLI {} with languages: {}",
+        lock.cli_version,
+        lock.languages.join(", ")
+    ))?;
+    let enabled: Vec<&str> = lock
+        .options
+        .iter()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !enabled.is_empty() {
+        ui::log::info(format!("Template options: {}", enabled.join(", ")))?;
+    }
+
+    let statuses = lock.status(&project_dir).await;
+    let mut unchanged = 0;
+    let mut modified = 0;
+    let mut deleted = 0;
+    for status in &statuses {
+        match status.state {
+            FileState::Unchanged => unchanged += 1,
+            FileState::Modified => {
+                modified += 1;
+                ui::log::warning(format!("{}: {}", status.path, status.state.label()))?;
+            }
+            FileState::Deleted => {
+                deleted += 1;
+                ui::log::error(format!("{}: {}", status.path, status.state.label()))?;
+            }
+        }
+    }
+
+    ui::outro(format!(
+        "{} generated files: {} unchanged, {} modified, {} deleted",
+        statuses.len(),
+        unchanged,
+        modified,
+        deleted
+    ))?;
+
+    Ok(())
+}
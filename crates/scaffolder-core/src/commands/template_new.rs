@@ -0,0 +1,299 @@
+//! `template new` command: interactive wizard for authoring a template in a registry
+
+use crate::product::ProductConfig;
+use crate::runtime::check::Language;
+use crate::templates::authoring::{self, ManifestDraft, STARTER_LANGUAGES};
+use crate::templates::manifest::RootManifest;
+use crate::templates::validate;
+use crate::ui;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// CLI arguments for the template new command
+#[derive(Debug, Clone, Default)]
+pub struct TemplateNewArgs {
+    /// Registry directory containing the root template.yaml
+    pub template_dir: Option<PathBuf>,
+
+    /// Folder name of the new template
+    pub id: Option<String>,
+}
+
+const LANGUAGES: [(&str, Language); 4] = [
+    ("typescript", Language::TypeScript),
+    ("javascript", Language::JavaScript),
+    ("python", Language::Python),
+    ("rust", Language::Rust),
+];
+
+/// Run the template new wizard
+pub async fn run_template_new<C: ProductConfig>(config: &C, args: TemplateNewArgs) -> Result<()> {
+    ui::intro(format!("{} template new", config.display_name()))?;
+
+    let registry = match &args.template_dir {
+        Some(dir) => dir.clone(),
+        None => {
+            let default = if Path::new("template.yaml").exists() {
+                "."
+            } else {
+                "templates"
+            };
+            let input: String = ui::input("Registry directory (containing template.yaml)")
+                .default_input(default)
+                .interact()?;
+            PathBuf::from(input)
+        }
+    };
+    let root_path = registry.join("template.yaml");
+    let root_yaml = std::fs::read_to_string(&root_path).with_context(|| {
+        format!(
+            "Root template.yaml not found in {} (pass --template-dir)",
+            registry.display()
+        )
+    })?;
+    let root: RootManifest =
+        serde_yaml::from_str(&root_yaml).context("Failed to parse root template.yaml")?;
+
+    let id = match &args.id {
+        Some(id) => {
+            if let Err(e) = check_template_id(&registry, id) {
+                anyhow::bail!("{}", e);
+            }
+            id.clone()
+        }
+        None => {
+            let registry = registry.clone();
+            ui::input("Template folder name")
+                .placeholder("my-template")
+                .validate(move |input: &String| check_template_id(&registry, input))
+                .interact()?
+        }
+    };
+
+    let name: String = ui::input("Display name")
+        .default_input(&title_case(&id))
+        .interact()?;
+    let description: String = ui::input("Description")
+        .placeholder("What does a project created from this template do?")
+        .interact()?;
+    let version: String = ui::input("Template version")
+        .default_input("0.1.0")
+        .validate(|input: &String| check_version(input, false))
+        .interact()?;
+    let min_iii_version = if config.requires_iii() {
+        let input: String = ui::input("Minimum iii version (optional)")
+            .placeholder("0.11.0")
+            .required(false)
+            .validate(|input: &String| check_version(input, true))
+            .interact()?;
+        Some(input.trim().to_string()).filter(|v| !v.is_empty())
+    } else {
+        None
+    };
+
+    let mut required = ui::multiselect("Required languages");
+    for (name, lang) in LANGUAGES {
+        required = required.item(name, lang.display_name(), "");
+    }
+    let requires: Vec<&str> = required.required(false).interact()?;
+
+    let remaining: Vec<(&str, Language)> = LANGUAGES
+        .into_iter()
+        .filter(|(name, _)| !requires.contains(name))
+        .collect();
+    let optional: Vec<&str> = if remaining.is_empty() {
+        Vec::new()
+    } else {
+        let mut select = ui::multiselect("Optional languages");
+        for (name, lang) in &remaining {
+            select = select.item(*name, lang.display_name(), "");
+        }
+        select.required(false).interact()?
+    };
+    if requires.is_empty() && optional.is_empty() {
+        anyhow::bail!("Select at least one language");
+    }
+
+    let starter_choices: Vec<Language> = LANGUAGES
+        .into_iter()
+        .filter(|(name, lang)| {
+            (requires.contains(name) || optional.contains(name)) && STARTER_LANGUAGES.contains(lang)
+        })
+        .map(|(_, lang)| lang)
+        .collect();
+    let mut workers: Vec<(Language, String)> = Vec::new();
+    if !starter_choices.is_empty() {
+        let mut select = ui::multiselect("Starter workers");
+        for lang in &starter_choices {
+            select = select.item(*lang, format!("{} worker", lang.display_name()), "");
+        }
+        let selected: Vec<Language> = select.required(false).interact()?;
+        for lang in selected {
+            let default = format!("{}-worker", lang.display_name().to_lowercase());
+            let taken: Vec<String> = workers.iter().map(|(_, n)| n.clone()).collect();
+            let worker_name: String =
+                ui::input(format!("Name for the {} worker", lang.display_name()))
+                    .default_input(&default)
+                    .validate(move |input: &String| check_worker_name(input, &taken))
+                    .interact()?;
+            workers.push((lang, worker_name));
+        }
+    }
+
+    // Collect every file the template ships
+    let mut files: Vec<(String, String)> = vec![(
+        "README.md".to_string(),
+        format!("# {}\n\n{}\n", name, description),
+    )];
+    for (lang, worker_name) in &workers {
+        files.extend(authoring::starter_worker(*lang, worker_name).unwrap_or_default());
+    }
+    let paths: Vec<String> = files.iter().map(|(path, _)| path.clone()).collect();
+
+    let mut draft = ManifestDraft {
+        name,
+        description,
+        version,
+        min_iii_version,
+        requires: requires.iter().map(|s| s.to_string()).collect(),
+        optional: optional.iter().map(|s| s.to_string()).collect(),
+        files: paths.clone(),
+        ..Default::default()
+    };
+    // Files the registry's patterns don't cover become template-level common files
+    draft.common_files = authoring::unclassified_files(&paths, &root.language_files);
+
+    // Fail before writing anything if the root manifest can't take the new entry
+    let registered = authoring::register_template(&root_yaml, &id)?;
+
+    let template_dir = registry.join(&id);
+    for (path, content) in &files {
+        let dest = template_dir.join(path);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        std::fs::write(&dest, content)
+            .with_context(|| format!("Failed to write {}", dest.display()))?;
+    }
+    std::fs::write(template_dir.join("template.yaml"), draft.render())
+        .with_context(|| format!("Failed to write {}/template.yaml", template_dir.display()))?;
+    ui::log::success(format!(
+        "Created {} with {} files",
+        template_dir.display(),
+        files.len()
+    ))?;
+
+    std::fs::write(&root_path, registered)
+        .with_context(|| format!("Failed to update {}", root_path.display()))?;
+    ui::log::success(format!("Registered '{}' in {}", id, root_path.display()))?;
+
+    let report = validate::validate_registry(&registry);
+    let problems: Vec<_> = report
+        .diagnostics
+        .iter()
+        .filter(|d| d.template.as_deref() == Some(id.as_str()))
+        .collect();
+    if problems.is_empty() {
+        ui::log::success("template.yaml passes validation")?;
+    } else {
+        for diagnostic in problems {
+            ui::log::warning(format!(
+                "{} [{}]",
+                diagnostic.message,
+                diagnostic.check.label()
+            ))?;
+        }
+    }
+
+    ui::outro(format!(
+        "Try it: {} create --template-dir {} --template {}",
+        config.binary_name(),
+        registry.display(),
+        id
+    ))?;
+
+    Ok(())
+}
+
+/// Template folder names are lowercase kebab-case and must not exist yet
+fn check_template_id(registry: &Path, id: &str) -> Result<(), String> {
+    if id.is_empty() {
+        return Err("Template folder name is required".to_string());
+    }
+    if !id
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        || id.starts_with('-')
+    {
+        return Err("Use lowercase letters, digits and dashes (e.g. my-template)".to_string());
+    }
+    if registry.join(id).exists() {
+        return Err(format!("{} already exists", registry.join(id).display()));
+    }
+    Ok(())
+}
+
+fn check_version(input: &str, optional: bool) -> Result<(), String> {
+    let input = input.trim();
+    if optional && input.is_empty() {
+        return Ok(());
+    }
+    semver::Version::parse(input)
+        .map(|_| ())
+        .map_err(|_| format!("'{}' is not a semver version (e.g. 0.1.0)", input))
+}
+
+fn check_worker_name(name: &str, taken: &[String]) -> Result<(), String> {
+    if name.is_empty() || name.contains(['/', '\\', ' ']) || name.starts_with('.') {
+        return Err("Use a plain folder name (e.g. api-worker)".to_string());
+    }
+    if taken.iter().any(|t| t == name) {
+        return Err(format!("Another worker is already named {}", name));
+    }
+    Ok(())
+}
+
+/// `my-api-template` -> `My Api Template`
+fn title_case(id: &str) -> String {
+    id.split('-')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_ids_are_kebab_case_and_new() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("taken")).unwrap();
+
+        assert!(check_template_id(tmp.path(), "my-api").is_ok());
+        assert!(check_template_id(tmp.path(), "My API").is_err());
+        assert!(check_template_id(tmp.path(), "-x").is_err());
+        assert!(check_template_id(tmp.path(), "taken").is_err());
+    }
+
+    #[test]
+    fn versions_must_be_semver() {
+        assert!(check_version("0.1.0", false).is_ok());
+        assert!(check_version("", true).is_ok());
+        assert!(check_version("", false).is_err());
+        assert!(check_version("1.0", true).is_err());
+    }
+
+    #[test]
+    fn title_case_splits_on_dashes() {
+        assert_eq!(title_case("my-api-template"), "My Api Template");
+    }
+}
@@ -0,0 +1,312 @@
+//! `upgrade` command: re-apply a newer template version to an existing project
+
+use super::create::setup_fetcher;
+use crate::product::ProductConfig;
+use crate::project::lock::{self, ScaffoldLock};
+use crate::project::merge::MergeLabels;
+use crate::project::upgrade::{self, FileOutcome};
+use crate::project::{self, ProjectInfo};
+use crate::runtime::check::Language;
+use crate::telemetry;
+use crate::templates::copier::{self, RenderedFile};
+use crate::templates::fetcher::TemplateFetcher;
+use crate::templates::manifest::{self, OptionValues, TemplateManifest, VariableValues};
+use crate::templates::version;
+use crate::ui;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+/// CLI arguments for the upgrade command
+#[derive(Debug, Clone, Default)]
+pub struct UpgradeArgs {
+    /// Local directory to use for templates instead of fetching from remote
+    pub template_dir: Option<PathBuf>,
+
+    /// Project directory to upgrade (defaults to the current directory)
+    pub directory: Option<PathBuf>,
+
+    /// Languages the project uses (detected from the template snapshot when omitted)
+    pub languages: Option<Vec<String>>,
+
+    /// Template option assignments overriding the detected values
+    pub options: Vec<String>,
+
+    /// Template variable assignments overriding the recorded values
+    pub variables: Vec<String>,
+
+    /// Auto-confirm all prompts (non-interactive mode)
+    pub yes: bool,
+}
+
+/// Run the upgrade command
+pub async fn run_upgrade<C: ProductConfig>(
+    config: &C,
+    args: UpgradeArgs,
+    cli_version: &str,
+) -> Result<()> {
+    ui::intro(format!("{} upgrade", config.display_name()))?;
+
+    let project_dir = project::resolve_project_dir(args.directory.as_deref());
+    let info = ProjectInfo::read(&project_dir).await?;
+    let snapshot_files = project::base_snapshot_files(&project_dir);
+
+    let mut fetcher = setup_fetcher(config, &args.template_dir)?;
+
+    let spinner = ui::spinner();
+    spinner.start("Loading template...");
+    let root_manifest = fetcher.fetch_root_manifest().await?;
+    let manifest = match fetcher.fetch_template_manifest(&info.source).await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            spinner.stop("Failed to load template");
+            return Err(e);
+        }
+    };
+    spinner.stop(format!(
+        "Template: {} - {}",
+        manifest.name, manifest.description
+    ));
+
+    if let Some(warning) =
+        version::check_compatibility(cli_version, &manifest.version, config.upgrade_command())
+    {
+        ui::log::warning(format!(
+            "Version warning: {}",
+            warning.lines().next().unwrap_or(&warning)
+        ))?;
+    }
+
+    let current_version = info.version.as_deref().unwrap_or("unknown");
+    if info.version.as_deref() == Some(manifest.version.as_str()) {
+        ui::outro(format!("Already on {} {}", info.source, manifest.version))?;
+        return Ok(());
+    }
+
+    // Projects scaffolded before the lockfile existed fall back to inferring from the snapshot
+    let lock = ScaffoldLock::read(&project_dir).await.ok();
+
+    let language_files = root_manifest.language_files_for(&manifest);
+
+    let selected_languages = match &args.languages {
+        Some(names) => {
+            let mut languages = Vec::new();
+            for name in names {
+                match Language::parse(name) {
+                    Some(language) if !languages.contains(&language) => languages.push(language),
+                    Some(_) => {}
+                    None => ui::log::warning(format!("Unknown language: {}", name))?,
+                }
+            }
+            languages
+        }
+        None => match &lock {
+            Some(lock) => lock.selected_languages(),
+            None => upgrade::infer_languages(&snapshot_files, &manifest, &language_files),
+        },
+    };
+    if selected_languages.is_empty() {
+        anyhow::bail!("Could not determine the project's languages. Pass them with --languages.");
+    }
+
+    let mut answers: OptionValues = upgrade::infer_options(&snapshot_files, &manifest);
+    if let Some(lock) = &lock {
+        // Options recorded at scaffold time win over inference; ones the template dropped are ignored
+        for (name, value) in &lock.options {
+            if manifest.option(name).is_some() {
+                answers.insert(name.clone(), *value);
+            }
+        }
+    }
+    for assignment in &args.options {
+        let (name, value) = manifest::parse_option_assignment(assignment)?;
+        answers.insert(name, value);
+    }
+    let options = manifest.resolve_options(&answers)?;
+
+    let mut variable_answers = lock
+        .as_ref()
+        .map(|lock| recorded_variables(&manifest, &lock.variables))
+        .unwrap_or_default();
+    for assignment in &args.variables {
+        let (name, value) = manifest::parse_variable_assignment(assignment)?;
+        if manifest.variable(&name).is_none() {
+            anyhow::bail!("Unknown variable '{}'", name);
+        }
+        variable_answers.insert(name, value);
+    }
+    let variables = manifest.resolve_variables(&variable_answers)?;
+
+    let lang_names: Vec<&str> = selected_languages
+        .iter()
+        .map(|l| l.display_name())
+        .collect();
+    ui::log::info(format!("Project languages: {}", lang_names.join(", ")))?;
+
+    // Without a snapshot (e.g. it was not committed), the template version the project
+    // was created from, rendered again, is the merge base
+    let base_files = if snapshot_files.is_empty() {
+        let Some(version) = info.version.as_deref() else {
+            anyhow::bail!(
+                "No template snapshot found in {} and no template version recorded to rebuild it from.",
+                project_dir.join(project::BASE_SNAPSHOT_DIR).display()
+            );
+        };
+        ui::log::info(format!(
+            "No template snapshot found, using {} {} as the merge base",
+            info.source, version
+        ))?;
+        Some(
+            render_base(
+                &mut fetcher,
+                &info.source,
+                version,
+                &selected_languages,
+                &options,
+                &variables,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+
+    if !args.yes {
+        let confirm: bool = ui::confirm(format!(
+            "Upgrade {} from {} to {}?",
+            info.source, current_version, manifest.version
+        ))
+        .initial_value(true)
+        .interact()?;
+        if !confirm {
+            anyhow::bail!("Upgrade cancelled.");
+        }
+    }
+
+    let spinner = ui::spinner();
+    spinner.start("Upgrading project...");
+
+    if let Some(base_files) = &base_files {
+        project::write_base_snapshot(&project_dir, base_files).await?;
+    }
+
+    let mut rendered = copier::render_template(
+        &mut fetcher,
+        &info.source,
+        &manifest,
+        &selected_languages,
+        &options,
+        &language_files,
+    )
+    .await?;
+    copier::apply_variables(&mut rendered, &variables)?;
+
+    let base_label = format!("{} {}", info.source, current_version);
+    let theirs_label = format!("{} {}", info.source, manifest.version);
+    let labels = MergeLabels {
+        ours: "local",
+        base: &base_label,
+        theirs: &theirs_label,
+    };
+    let report = upgrade::upgrade_project(&project_dir, &rendered, labels).await?;
+
+    telemetry::write_project_ini(
+        &project_dir,
+        &info.project_id,
+        &info.project_name,
+        &info.source,
+        &manifest.version,
+    )
+    .await?;
+
+    let new_lock = ScaffoldLock {
+        template: info.source.clone(),
+        template_version: manifest.version.clone(),
+        registry: fetcher.source().redacted(),
+        cli_version: cli_version.to_string(),
+        languages: lock::language_names(&selected_languages),
+        options,
+        variables,
+        files: lock::hash_files(&rendered),
+    };
+    new_lock.write(&project_dir).await?;
+
+    spinner.stop(format!("Upgraded {} to {}", info.source, manifest.version));
+
+    for change in &report.changes {
+        if change.outcome == FileOutcome::Unchanged {
+            continue;
+        }
+        let line = format!("{}: {}", change.path, change.outcome.label());
+        if change.outcome.needs_attention() {
+            ui::log::warning(line)?;
+        } else {
+            ui::log::info(line)?;
+        }
+    }
+
+    let conflicts = report.conflicts();
+    if conflicts.is_empty() {
+        ui::outro("Upgrade complete")?;
+    } else {
+        ui::outro(format!(
+            "Upgrade complete with {} conflicting file(s). Resolve the conflict markers before continuing.",
+            conflicts.len()
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Render the template version a project was created from, with the project's selections
+async fn render_base(
+    fetcher: &mut TemplateFetcher,
+    template: &str,
+    version: &str,
+    selected_languages: &[Language],
+    options: &OptionValues,
+    variables: &VariableValues,
+) -> Result<Vec<RenderedFile>> {
+    let name = TemplateFetcher::versioned_name(template, version);
+    let manifest = fetcher
+        .fetch_template_manifest(&name)
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to fetch {} {} to use as the merge base",
+                template, version
+            )
+        })?;
+    let language_files = fetcher
+        .fetch_root_manifest()
+        .await?
+        .language_files_for(&manifest);
+    // Options the older version did not have yet are left out
+    let answers: OptionValues = options
+        .iter()
+        .filter(|(name, _)| manifest.option(name).is_some())
+        .map(|(name, value)| (name.clone(), *value))
+        .collect();
+    let options = manifest.resolve_options(&answers)?;
+    let variables = manifest.resolve_variables(&recorded_variables(&manifest, variables))?;
+
+    let mut rendered = copier::render_template(
+        fetcher,
+        &name,
+        &manifest,
+        selected_languages,
+        &options,
+        &language_files,
+    )
+    .await?;
+    copier::apply_variables(&mut rendered, &variables)?;
+    Ok(rendered)
+}
+
+/// Variable values the manifest still declares; ones it dropped are ignored
+fn recorded_variables(manifest: &TemplateManifest, values: &VariableValues) -> VariableValues {
+    values
+        .iter()
+        .filter(|(name, _)| manifest.variable(name).is_some())
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
@@ -1,5 +1,10 @@
-//! Configuration file generation utilities
+//! Configuration utilities
+//!
+//! - `generator`: values for generated configuration files
+//! - `user`: user and project-level defaults read from config files
 
 pub mod generator;
+pub mod user;
 
 pub use generator::detect_js_runtime;
+pub use user::{PackageManager, UserConfig};
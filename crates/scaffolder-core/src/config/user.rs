@@ -0,0 +1,339 @@
+//! User-level defaults read from config files
+//!
+//! Settings come from `~/.config/<binary>/config.yaml` (or `$XDG_CONFIG_HOME/<binary>/config.yaml`)
+//! and from a project-local `.iii/<binary>.yaml` found in the working directory or one of its
+//! parents; project-local values win. Command-line flags and environment variables take
+//! precedence over both.
+//!
+//! `template_url` and `registries` are only read from the user file: a checked-out
+//! repository must not be able to redirect downloads or choose which secrets get sent.
+
+use crate::product::ProductConfig;
+use crate::project::PROJECT_META_DIR;
+use crate::runtime::check::Language;
+use crate::templates::copier::ConflictPolicy;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Package manager used to install JavaScript dependencies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    #[default]
+    Npm,
+    Pnpm,
+    Yarn,
+    Bun,
+}
+
+impl PackageManager {
+    /// Executable name; each of them installs with `<name> install`
+    pub fn name(self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Bun => "bun",
+        }
+    }
+}
+
+/// A bearer token sent with requests to a template registry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryAuth {
+    /// Registry URL the token applies to: same scheme, host and port, and a path at or
+    /// below this one
+    pub url: String,
+
+    /// Token stored in the file
+    #[serde(default)]
+    pub token: Option<String>,
+
+    /// Environment variable holding the token (preferred over `token` when set)
+    #[serde(default)]
+    pub token_env: Option<String>,
+}
+
+impl RegistryAuth {
+    /// The token to send, if one is configured
+    pub fn resolve_token(&self) -> Option<String> {
+        self.token_env
+            .as_deref()
+            .and_then(|var| std::env::var(var).ok())
+            .or_else(|| self.token.clone())
+            .filter(|token| !token.is_empty())
+    }
+}
+
+/// Defaults from the user and project config files
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UserConfig {
+    /// Template registry URL used instead of the product default (user file only)
+    pub template_url: Option<String>,
+
+    /// Languages preselected when `--languages` is not given (and used as-is with `--yes`)
+    pub languages: Option<Vec<String>>,
+
+    /// Package manager for JavaScript dependencies
+    pub package_manager: Option<PackageManager>,
+
+    /// Default for `--on-conflict`
+    pub on_conflict: Option<ConflictPolicy>,
+
+    /// `false` turns off usage telemetry
+    pub telemetry: Option<bool>,
+
+    /// Registry credentials, matched against request URLs by prefix (user file only)
+    pub registries: Vec<RegistryAuth>,
+
+    /// Files the settings were read from, most specific first
+    #[serde(skip)]
+    pub sources: Vec<PathBuf>,
+}
+
+/// `~/.config/<binary>/config.yaml`, honoring `$XDG_CONFIG_HOME`
+pub fn user_config_path(binary_name: &str) -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))?;
+    Some(config_home.join(binary_name).join("config.yaml"))
+}
+
+/// Nearest `.iii/<binary>.yaml` at or above `start`, not counting the home directory
+pub fn project_config_path(start: &Path, binary_name: &str) -> Option<PathBuf> {
+    let home = dirs::home_dir();
+    start
+        .ancestors()
+        .take_while(|dir| home.as_deref() != Some(*dir))
+        .map(|dir| {
+            dir.join(PROJECT_META_DIR)
+                .join(format!("{}.yaml", binary_name))
+        })
+        .find(|path| path.is_file())
+}
+
+impl UserConfig {
+    /// Load the user and project config files for a product, relative to the working directory
+    pub fn load<C: ProductConfig>(config: &C) -> Result<Self> {
+        let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let user_path = user_config_path(config.binary_name());
+        let project_path = project_config_path(&current_dir, config.binary_name());
+        Self::load_from(user_path.as_deref(), project_path.as_deref())
+    }
+
+    /// Load config files, where project settings override user settings; missing files are skipped
+    pub fn load_from(user_path: Option<&Path>, project_path: Option<&Path>) -> Result<Self> {
+        let mut config = UserConfig::default();
+        if let Some(path) = user_path.filter(|path| path.is_file()) {
+            config = Self::read(path)?;
+        }
+        if let Some(path) = project_path.filter(|path| path.is_file()) {
+            let project = Self::read(path)?;
+            if project.template_url.is_some() || !project.registries.is_empty() {
+                anyhow::bail!(
+                    "template_url and registries can only be set in the user config file, not in {}",
+                    path.display()
+                );
+            }
+            config = config.merge(project);
+        }
+        Ok(config)
+    }
+
+    /// Read and check one config file
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        // An empty file is a valid (empty) config
+        let mut config: UserConfig = if content.trim().is_empty() {
+            UserConfig::default()
+        } else {
+            serde_yaml::from_str(&content)
+                .with_context(|| format!("Failed to parse config file {}", path.display()))?
+        };
+
+        for language in config.languages.iter().flatten() {
+            if Language::parse(language).is_none() {
+                anyhow::bail!(
+                    "Unknown language '{}' in config file {}",
+                    language,
+                    path.display()
+                );
+            }
+        }
+        if let Some(url) = &config.template_url {
+            url::Url::parse(url)
+                .with_context(|| format!("Invalid template_url '{}' in {}", url, path.display()))?;
+        }
+        for registry in &config.registries {
+            url::Url::parse(&registry.url).with_context(|| {
+                format!(
+                    "Invalid registry url '{}' in {}",
+                    registry.url,
+                    path.display()
+                )
+            })?;
+        }
+
+        config.sources = vec![path.to_path_buf()];
+        Ok(config)
+    }
+
+    /// Layer `other` on top of `self`; `other`'s values and registries take precedence
+    pub fn merge(self, other: UserConfig) -> UserConfig {
+        UserConfig {
+            template_url: other.template_url.or(self.template_url),
+            languages: other.languages.or(self.languages),
+            package_manager: other.package_manager.or(self.package_manager),
+            on_conflict: other.on_conflict.or(self.on_conflict),
+            telemetry: other.telemetry.or(self.telemetry),
+            registries: other
+                .registries
+                .into_iter()
+                .chain(self.registries)
+                .collect(),
+            sources: other.sources.into_iter().chain(self.sources).collect(),
+        }
+    }
+
+    /// Preferred languages, in the order given
+    pub fn preferred_languages(&self) -> Vec<Language> {
+        self.languages
+            .iter()
+            .flatten()
+            .filter_map(|name| Language::parse(name))
+            .collect()
+    }
+
+    /// Whether telemetry is allowed by the config files (environment opt-outs still apply)
+    pub fn telemetry_enabled(&self) -> bool {
+        self.telemetry != Some(false)
+    }
+
+    /// Configured registry tokens as `(url prefix, token)` pairs, most specific file first
+    pub fn registry_tokens(&self) -> Vec<(String, String)> {
+        self.registries
+            .iter()
+            .filter_map(|registry| Some((registry.url.clone(), registry.resolve_token()?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn project_config_overrides_user_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let user = tmp.path().join("user/config.yaml");
+        let project = tmp.path().join("repo/.iii/iii-tools.yaml");
+        write(
+            &user,
+            "template_url: https://templates.example.com/iii\nlanguages: [ts, py]\npackage_manager: pnpm\nregistries:\n  - url: https://templates.example.com/\n    token: user-token\n",
+        );
+        write(
+            &project,
+            "languages: [python]\non_conflict: backup\ntelemetry: false\n",
+        );
+
+        let config = UserConfig::load_from(Some(&user), Some(&project)).unwrap();
+
+        assert_eq!(
+            config.template_url.as_deref(),
+            Some("https://templates.example.com/iii")
+        );
+        assert_eq!(config.preferred_languages(), vec![Language::Python]);
+        assert_eq!(config.package_manager, Some(PackageManager::Pnpm));
+        assert_eq!(config.on_conflict, Some(ConflictPolicy::Backup));
+        assert!(!config.telemetry_enabled());
+        assert_eq!(
+            config.registry_tokens(),
+            vec![(
+                "https://templates.example.com/".to_string(),
+                "user-token".to_string()
+            )]
+        );
+        assert_eq!(config.sources, vec![project, user]);
+    }
+
+    #[test]
+    fn project_config_cannot_set_registry_or_credentials() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project = tmp.path().join("repo/.iii/iii-tools.yaml");
+
+        for content in [
+            "template_url: https://evil.example.com/\n",
+            "registries:\n  - url: https://evil.example.com/\n    token_env: GITHUB_TOKEN\n",
+        ] {
+            write(&project, content);
+            let err = UserConfig::load_from(None, Some(&project)).unwrap_err();
+            assert!(err.to_string().contains("user config file"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn missing_and_empty_files_give_defaults() {
+        let tmp = tempfile::tempdir().unwrap();
+        let empty = tmp.path().join("config.yaml");
+        write(&empty, "");
+
+        let config =
+            UserConfig::load_from(Some(&tmp.path().join("missing.yaml")), Some(&empty)).unwrap();
+        assert_eq!(config.template_url, None);
+        assert!(config.telemetry_enabled());
+        assert_eq!(config.sources, vec![empty]);
+    }
+
+    #[test]
+    fn invalid_settings_are_errors() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.yaml");
+
+        write(&path, "languages: [cobol]\n");
+        assert!(UserConfig::read(&path)
+            .unwrap_err()
+            .to_string()
+            .contains("cobol"));
+
+        write(&path, "template_url: not a url\n");
+        assert!(UserConfig::read(&path).is_err());
+
+        write(&path, "package_manager: pip\n");
+        assert!(UserConfig::read(&path).is_err());
+
+        write(&path, "registry: https://example.com\n");
+        assert!(UserConfig::read(&path).is_err());
+    }
+
+    #[test]
+    fn project_config_is_found_in_parent_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(".iii/iii-tools.yaml");
+        write(&path, "telemetry: false\n");
+        let nested = tmp.path().join("apps/web");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(project_config_path(&nested, "iii-tools"), Some(path));
+        assert_eq!(project_config_path(&nested, "motia"), None);
+    }
+
+    #[test]
+    fn token_falls_back_to_file_when_env_is_unset() {
+        let auth = RegistryAuth {
+            url: "https://example.com/".to_string(),
+            token: Some("from-file".to_string()),
+            token_env: Some("SCAFFOLDER_TEST_REGISTRY_TOKEN_UNSET".to_string()),
+        };
+        assert_eq!(auth.resolve_token().as_deref(), Some("from-file"));
+    }
+}
@@ -0,0 +1,184 @@
+//! Progress events from the scaffolding pipeline
+//!
+//! The pipeline reports what it does as [`Event`]s sent to an [`EventSink`] instead of
+//! drawing spinners or printing itself. Frontends render them their own way: the TUI as
+//! spinners and log lines, [`JsonLines`] as one JSON object per line, a GUI however it likes.
+//! [`NoEvents`] drops them.
+
+use crate::runtime::check::RuntimeInfo;
+use crate::telemetry::DependencyInstall;
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Something the pipeline started, finished or produced
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// A template's manifest was loaded from the registry
+    TemplateFetched {
+        template: String,
+        version: String,
+    },
+    RenderStarted,
+    TemplateRendered {
+        files: usize,
+    },
+    /// An existing file was kept instead of the template's version
+    FileSkipped {
+        path: String,
+    },
+    /// An existing file was renamed before being overwritten
+    FileBackedUp {
+        path: String,
+        backup: String,
+    },
+    WriteStarted {
+        project_dir: PathBuf,
+    },
+    /// A file was moved into the project (project-relative path)
+    FileWritten {
+        path: String,
+    },
+    ProjectWritten {
+        project_dir: PathBuf,
+        files: usize,
+    },
+    /// A file the user already had was merged with the template's version
+    FileMerged {
+        path: String,
+        summary: String,
+    },
+    RuntimeCheckStarted,
+    RuntimeDetected {
+        runtime: RuntimeInfo,
+    },
+    /// `missing` describes the runtimes that must be installed first
+    RuntimeCheckFinished {
+        missing: Option<String>,
+    },
+    HookStarted {
+        command: String,
+    },
+    HookFinished {
+        command: String,
+        required: bool,
+        error: Option<String>,
+    },
+    /// A line printed by a hook, installer or package manager
+    ProcessOutput {
+        line: String,
+        stderr: bool,
+    },
+    DependencyInstallStarted {
+        project_dir: PathBuf,
+    },
+    DependencyInstallFinished {
+        installs: Vec<DependencyInstall>,
+        error: Option<String>,
+    },
+    /// A telemetry event went out; `completed` is false when it was still pending at the deadline
+    TelemetrySent {
+        name: String,
+        completed: bool,
+    },
+    /// A failed run removed the files it had created
+    RolledBack {
+        removed: usize,
+    },
+    ToolInstallStarted {
+        tool: String,
+        command: String,
+    },
+    ToolInstallFinished {
+        tool: String,
+        error: Option<String>,
+    },
+}
+
+/// Receives pipeline events
+///
+/// Implemented for closures, so `&|event| tx.send(event).unwrap()` is a sink.
+pub trait EventSink: Send + Sync {
+    fn emit(&self, event: Event);
+}
+
+impl<F: Fn(Event) + Send + Sync> EventSink for F {
+    fn emit(&self, event: Event) {
+        self(event)
+    }
+}
+
+/// Discards every event
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoEvents;
+
+impl EventSink for NoEvents {
+    fn emit(&self, _event: Event) {}
+}
+
+/// Writes each event as a line of JSON
+pub struct JsonLines<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonLines<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<W: Write + Send> EventSink for JsonLines<W> {
+    fn emit(&self, event: Event) {
+        let Ok(line) = serde_json::to_string(&event) else {
+            return;
+        };
+        let mut writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // Progress output is best-effort; a closed pipe must not fail the run
+        let _ = writeln!(writer, "{}", line);
+        let _ = writer.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_lines_tags_each_event() {
+        let sink = JsonLines::new(Vec::new());
+        sink.emit(Event::TemplateRendered { files: 3 });
+        sink.emit(Event::FileWritten {
+            path: "src/index.ts".to_string(),
+        });
+
+        let output = String::from_utf8(sink.into_inner()).unwrap();
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec![
+                r#"{"event":"template_rendered","files":3}"#,
+                r#"{"event":"file_written","path":"src/index.ts"}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn closures_are_sinks() {
+        let events = Mutex::new(Vec::new());
+        let sink = |event: Event| events.lock().unwrap().push(event);
+        sink.emit(Event::RenderStarted);
+        assert_eq!(*events.lock().unwrap(), vec![Event::RenderStarted]);
+    }
+}
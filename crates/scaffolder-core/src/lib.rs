@@ -10,11 +10,16 @@
 //!
 //! - **Layer 1: Core Operations** - Pure functions for template fetching, copying, runtime detection
 //! - **Layer 2: Workflow Orchestration** - `ProductConfig` trait and `ProjectBuilder` for custom UIs
-//! - **Layer 3: CLI/TUI Interface** - Optional cliclack-based prompts (feature-gated)
+//! - **Layer 3: CLI Interface** - The commands and the shared command-line interface, with
+//!   cliclack prompts or plain-text ones depending on the `tui` feature
+//!
+//! A product is either a Rust `ProductConfig` impl or a YAML file loaded as a
+//! `ProductDefinition`; binaries hand it to `cli::main`.
 //!
 //! # Feature Flags
 //!
-//! - `tui` (default): Enables the cliclack-based TUI prompts module
+//! - `tui` (default): Uses cliclack's Charm-style prompts and spinners. Without it the same
+//!   commands ask and report with plain lines on stdin/stderr, for a minimal-dependency build
 //!
 //! # Example Usage (without TUI)
 //!
@@ -38,25 +43,31 @@
 //! let fetcher = TemplateFetcher::from_config(&MyConfig).unwrap();
 //! ```
 
+pub mod builder;
+pub mod cli;
+pub mod commands;
 pub mod config;
+pub mod events;
 pub mod product;
+pub mod project;
+pub mod prompter;
 pub mod runtime;
 pub mod telemetry;
 pub mod templates;
-
-#[cfg(feature = "tui")]
-pub mod tui;
+pub mod ui;
 
 // Re-export main types for convenience
-pub use product::ProductConfig;
+pub use builder::{BuildError, CreateReport, ProjectBuilder};
+pub use commands::run;
+pub use events::{Event, EventSink};
+pub use product::{ProductConfig, ProductDefinition};
+pub use prompter::{AnswersPrompter, Prompter};
 pub use runtime::{check_runtimes, Language, RuntimeInfo};
 pub use templates::{
-    copy_template, LanguageFiles, RootManifest, TemplateFetcher, TemplateManifest, TemplateSource,
+    copy_template, LanguageFiles, OptionValues, RootManifest, TemplateFetcher, TemplateManifest,
+    TemplateSource,
 };
 
-#[cfg(feature = "tui")]
-pub use tui::run;
-
 /// CLI version - used for template compatibility checking
 /// Each binary should define its own version, but this provides a fallback
 pub const DEFAULT_CLI_VERSION: &str = "0.1.0";
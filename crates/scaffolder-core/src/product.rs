@@ -1,7 +1,11 @@
 //! Product configuration trait for CLI binaries
 //!
 //! This trait defines the interface that each product (motia, iii) must implement
-//! to configure the scaffolding behavior for their specific needs.
+//! to configure the scaffolding behavior for their specific needs. Products can also be
+//! defined in YAML and loaded as a [`ProductDefinition`].
+
+use anyhow::{Context, Result};
+use std::path::Path;
 
 /// Configuration trait for different CLI products
 ///
@@ -39,4 +43,222 @@ pub trait ProductConfig: Clone + Send + Sync + 'static {
     fn user_agent(&self) -> &'static str {
         self.name()
     }
+
+    /// Executable name shown in suggested commands (defaults to the product name)
+    fn binary_name(&self) -> &'static str {
+        self.name()
+    }
+}
+
+/// Tools a product definition can list under `requires`
+pub const KNOWN_TOOLS: &[&str] = &["iii"];
+
+/// A product described in YAML instead of a Rust `ProductConfig` impl
+///
+/// ```yaml
+/// name: acme
+/// display_name: Acme
+/// binary_name: acme-tools          # optional, defaults to name
+/// description: CLI for scaffolding Acme projects
+/// template_url: https://templates.acme.dev/registry
+/// template_url_env: ACME_TEMPLATE_URL
+/// docs_url: https://acme.dev/docs
+/// upgrade_command: brew upgrade acme-tools
+/// requires: [iii]                  # optional, tools checked before create
+/// ```
+///
+/// A definition lives for the whole process, so its strings are leaked once when it is parsed
+/// to satisfy the `&'static str` accessors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductDefinition {
+    name: &'static str,
+    display_name: &'static str,
+    binary_name: &'static str,
+    description: &'static str,
+    template_url: &'static str,
+    template_url_env: &'static str,
+    docs_url: &'static str,
+    upgrade_command: &'static str,
+    user_agent: &'static str,
+    requires: Vec<&'static str>,
+}
+
+/// On-disk form of a product definition
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProductFile {
+    name: String,
+    display_name: String,
+    #[serde(default)]
+    binary_name: Option<String>,
+    description: String,
+    template_url: String,
+    template_url_env: String,
+    docs_url: String,
+    upgrade_command: String,
+    #[serde(default)]
+    user_agent: Option<String>,
+    #[serde(default)]
+    requires: Vec<String>,
+}
+
+fn leak(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+impl ProductDefinition {
+    /// Parse and check a product definition
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let file: ProductFile =
+            serde_yaml::from_str(content).context("Invalid product definition")?;
+
+        let is_command_name = |s: &str| {
+            !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        };
+        if !is_command_name(&file.name) {
+            anyhow::bail!(
+                "Product name '{}' must use lowercase letters, digits, '-' or '_'",
+                file.name
+            );
+        }
+        if let Some(binary_name) = &file.binary_name {
+            if !is_command_name(binary_name) {
+                anyhow::bail!(
+                    "binary_name '{}' must use lowercase letters, digits, '-' or '_'",
+                    binary_name
+                );
+            }
+        }
+        if file.template_url_env.is_empty()
+            || !file
+                .template_url_env
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        {
+            anyhow::bail!(
+                "template_url_env '{}' must be an upper-case environment variable name",
+                file.template_url_env
+            );
+        }
+        url::Url::parse(&file.template_url)
+            .with_context(|| format!("Invalid template_url '{}'", file.template_url))?;
+        for tool in &file.requires {
+            if !KNOWN_TOOLS.contains(&tool.as_str()) {
+                anyhow::bail!(
+                    "Unknown required tool '{}'. Supported tools: {}",
+                    tool,
+                    KNOWN_TOOLS.join(", ")
+                );
+            }
+        }
+
+        let name = leak(file.name);
+        Ok(ProductDefinition {
+            name,
+            display_name: leak(file.display_name),
+            binary_name: file.binary_name.map(leak).unwrap_or(name),
+            description: leak(file.description),
+            template_url: leak(file.template_url),
+            template_url_env: leak(file.template_url_env),
+            docs_url: leak(file.docs_url),
+            upgrade_command: leak(file.upgrade_command),
+            user_agent: file.user_agent.map(leak).unwrap_or(name),
+            requires: file.requires.into_iter().map(leak).collect(),
+        })
+    }
+
+    /// Read a product definition file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read product definition {}", path.display()))?;
+        Self::from_yaml(&content).with_context(|| format!("In {}", path.display()))
+    }
+}
+
+impl ProductConfig for ProductDefinition {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn display_name(&self) -> &'static str {
+        self.display_name
+    }
+
+    fn default_template_url(&self) -> &'static str {
+        self.template_url
+    }
+
+    fn template_url_env(&self) -> &'static str {
+        self.template_url_env
+    }
+
+    fn requires_iii(&self) -> bool {
+        self.requires.contains(&"iii")
+    }
+
+    fn docs_url(&self) -> &'static str {
+        self.docs_url
+    }
+
+    fn cli_description(&self) -> &'static str {
+        self.description
+    }
+
+    fn upgrade_command(&self) -> &'static str {
+        self.upgrade_command
+    }
+
+    fn user_agent(&self) -> &'static str {
+        self.user_agent
+    }
+
+    fn binary_name(&self) -> &'static str {
+        self.binary_name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACME: &str = "name: acme\ndisplay_name: Acme\ndescription: CLI for scaffolding Acme projects\ntemplate_url: https://templates.acme.dev/registry\ntemplate_url_env: ACME_TEMPLATE_URL\ndocs_url: https://acme.dev/docs\nupgrade_command: brew upgrade acme\n";
+
+    #[test]
+    fn definition_fills_in_defaults() {
+        let product = ProductDefinition::from_yaml(ACME).unwrap();
+        assert_eq!(product.name(), "acme");
+        assert_eq!(product.display_name(), "Acme");
+        assert_eq!(product.binary_name(), "acme");
+        assert_eq!(product.user_agent(), "acme");
+        assert_eq!(product.template_url_env(), "ACME_TEMPLATE_URL");
+        assert!(!product.requires_iii());
+
+        let product = ProductDefinition::from_yaml(&format!(
+            "{}binary_name: acme-tools\nrequires: [iii]\n",
+            ACME
+        ))
+        .unwrap();
+        assert_eq!(product.binary_name(), "acme-tools");
+        assert!(product.requires_iii());
+    }
+
+    #[test]
+    fn invalid_definitions_are_rejected() {
+        let cases = [
+            (format!("{}requires: [docker]\n", ACME), "docker"),
+            (ACME.replace("name: acme", "name: Acme Tools"), "Acme Tools"),
+            (ACME.replace("ACME_TEMPLATE_URL", "acme-url"), "acme-url"),
+            (
+                ACME.replace("https://templates.acme.dev/registry", "templates"),
+                "templates",
+            ),
+            (format!("{}colour: red\n", ACME), "colour"),
+        ];
+        for (yaml, mention) in cases {
+            let err = format!("{:#}", ProductDefinition::from_yaml(&yaml).unwrap_err());
+            assert!(err.contains(mention), "{}", err);
+        }
+    }
 }
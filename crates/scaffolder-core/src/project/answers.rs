@@ -0,0 +1,135 @@
+//! Answers files: the choices `create` asks for, kept in a file for reproducible scaffolding
+
+use crate::runtime::check::Language;
+use crate::templates::manifest::{OptionValues, VariableValues};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Contents of an answers file (`--answers` / `--save-answers`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    /// Template folder name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+
+    /// Project directory, relative to where the command runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory: Option<PathBuf>,
+
+    /// Languages to include; the template's required languages are always added.
+    /// An empty list selects no optional languages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub languages: Option<Vec<String>>,
+
+    /// Template option values; options left out fall back to prompts or defaults
+    #[serde(default, skip_serializing_if = "OptionValues::is_empty")]
+    pub options: OptionValues,
+
+    /// Template variable values; variables left out fall back to prompts or defaults
+    #[serde(default, skip_serializing_if = "VariableValues::is_empty")]
+    pub variables: VariableValues,
+
+    /// Run the template's post-create hooks without asking, like `--allow-hooks`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_hooks: bool,
+}
+
+impl Answers {
+    /// Read and check an answers file
+    pub async fn read(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .await
+            .with_context(|| format!("Failed to read answers file {}", path.display()))?;
+        let answers: Answers = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse answers file {}", path.display()))?;
+
+        for language in answers.languages.iter().flatten() {
+            if Language::parse(language).is_none() {
+                anyhow::bail!(
+                    "Unknown language '{}' in answers file {}. Use typescript, javascript, python or rust",
+                    language,
+                    path.display()
+                );
+            }
+        }
+
+        Ok(answers)
+    }
+
+    /// Write the answers as YAML, creating parent directories as needed
+    pub async fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let body = format!(
+            "# Answers for `create --answers {}`\n{}",
+            path.display(),
+            serde_yaml::to_string(self).context("serialize answers")?
+        );
+        fs::write(path, body)
+            .await
+            .with_context(|| format!("Failed to write answers file {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Options as `name=value` assignments, the form `--option` takes
+    pub fn option_assignments(&self) -> Vec<String> {
+        self.options
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect()
+    }
+
+    /// Variables as `name=value` assignments, the form `--var` takes
+    pub fn variable_assignments(&self) -> Vec<String> {
+        self.variables
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn answers_round_trip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("answers/app.yaml");
+        let answers = Answers {
+            template: Some("quickstart".to_string()),
+            directory: Some(PathBuf::from("my-app")),
+            languages: Some(vec!["typescript".to_string()]),
+            options: OptionValues::from([("docker".to_string(), false)]),
+            variables: VariableValues::from([("greeting".to_string(), "a=b".to_string())]),
+            allow_hooks: true,
+        };
+
+        answers.write(&path).await.unwrap();
+        assert_eq!(Answers::read(&path).await.unwrap(), answers);
+        assert_eq!(answers.option_assignments(), vec!["docker=false"]);
+        assert_eq!(answers.variable_assignments(), vec!["greeting=a=b"]);
+    }
+
+    #[tokio::test]
+    async fn answers_reject_unknown_fields_and_languages() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("answers.yaml");
+
+        std::fs::write(&path, "template: quickstart\nlanguage: [ts]\n").unwrap();
+        assert!(Answers::read(&path).await.is_err());
+
+        std::fs::write(&path, "languages: [ts, cobol]\n").unwrap();
+        let err = Answers::read(&path).await.unwrap_err();
+        assert!(err.to_string().contains("cobol"));
+
+        std::fs::write(&path, "languages: []\n").unwrap();
+        assert_eq!(Answers::read(&path).await.unwrap().languages, Some(vec![]));
+    }
+}
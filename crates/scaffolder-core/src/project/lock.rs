@@ -0,0 +1,221 @@
+//! Scaffold lockfile: what a project was generated from and what was generated
+
+use crate::runtime::check::Language;
+use crate::templates::copier::RenderedFile;
+use crate::templates::manifest::{OptionValues, VariableValues};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tokio::fs;
+
+/// Lockfile location, relative to the project root
+pub const LOCK_FILE: &str = ".iii/scaffold.lock";
+
+/// Contents of `.iii/scaffold.lock`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScaffoldLock {
+    /// Template the project was generated from
+    pub template: String,
+
+    /// Template version the generated files correspond to
+    pub template_version: String,
+
+    /// Template registry URL without credentials or query string (or local template directory)
+    pub registry: String,
+
+    /// Version of the CLI that generated the files
+    pub cli_version: String,
+
+    /// Selected languages (lowercase names, e.g. `typescript`)
+    #[serde(default)]
+    pub languages: Vec<String>,
+
+    /// Resolved template option values
+    #[serde(default)]
+    pub options: OptionValues,
+
+    /// Resolved template variable values
+    #[serde(default, skip_serializing_if = "VariableValues::is_empty")]
+    pub variables: VariableValues,
+
+    /// Content hash of every file as the template generated it, keyed by project-relative path
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+/// How a generated file compares to what was generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileState {
+    Unchanged,
+    Modified,
+    Deleted,
+}
+
+impl FileState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileState::Unchanged => "unchanged",
+            FileState::Modified => "modified",
+            FileState::Deleted => "deleted",
+        }
+    }
+}
+
+/// State of one generated file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStatus {
+    pub path: String,
+    pub state: FileState,
+}
+
+/// `sha256:<hex>` digest of file content
+pub fn content_hash(content: &[u8]) -> String {
+    let digest = Sha256::digest(content);
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256:{}", hex)
+}
+
+/// Hash every generated file, keyed by destination path
+pub fn hash_files(files: &[RenderedFile]) -> BTreeMap<String, String> {
+    files
+        .iter()
+        .map(|f| (f.dest.clone(), content_hash(&f.content)))
+        .collect()
+}
+
+/// Lowercase language names as stored in the lockfile
+pub fn language_names(languages: &[Language]) -> Vec<String> {
+    languages
+        .iter()
+        .map(|l| l.display_name().to_lowercase())
+        .collect()
+}
+
+impl ScaffoldLock {
+    /// Read the lockfile from a project directory
+    pub async fn read(project_dir: &Path) -> Result<Self> {
+        let path = project_dir.join(LOCK_FILE);
+        let content = fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_yaml::from_str(&content).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    /// Write the lockfile into a project directory
+    pub async fn write(&self, project_dir: &Path) -> Result<()> {
+        let path = project_dir.join(LOCK_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let content = serde_yaml::to_string(self).context("Failed to serialize scaffold lock")?;
+        fs::write(&path, content)
+            .await
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Selected languages, skipping names this version does not recognise
+    pub fn selected_languages(&self) -> Vec<Language> {
+        self.languages
+            .iter()
+            .filter_map(|name| Language::parse(name))
+            .collect()
+    }
+
+    /// Compare every generated file with what is on disk now
+    pub async fn status(&self, project_dir: &Path) -> Vec<FileStatus> {
+        let mut statuses = Vec::with_capacity(self.files.len());
+        for (path, hash) in &self.files {
+            let state = match fs::read(project_dir.join(path)).await {
+                Ok(content) if content_hash(&content) == *hash => FileState::Unchanged,
+                Ok(_) => FileState::Modified,
+                Err(_) => FileState::Deleted,
+            };
+            statuses.push(FileStatus {
+                path: path.clone(),
+                state,
+            });
+        }
+        statuses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock_for(files: &[RenderedFile]) -> ScaffoldLock {
+        ScaffoldLock {
+            template: "quickstart".to_string(),
+            template_version: "0.1.0".to_string(),
+            registry: "https://example.com/templates".to_string(),
+            cli_version: "0.6.3".to_string(),
+            languages: language_names(&[Language::TypeScript, Language::Python]),
+            options: OptionValues::from([("docker".to_string(), true)]),
+            variables: VariableValues::from([("service".to_string(), "billing".to_string())]),
+            files: hash_files(files),
+        }
+    }
+
+    #[test]
+    fn content_hash_is_sha256_hex() {
+        assert_eq!(
+            content_hash(b""),
+            "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[tokio::test]
+    async fn lock_round_trips_through_yaml() {
+        let tmp = tempfile::tempdir().unwrap();
+        let lock = lock_for(&[RenderedFile {
+            dest: "README.md".to_string(),
+            content: b"# App\n".to_vec(),
+        }]);
+
+        lock.write(tmp.path()).await.unwrap();
+        let read = ScaffoldLock::read(tmp.path()).await.unwrap();
+        assert_eq!(read, lock);
+        assert_eq!(
+            read.selected_languages(),
+            vec![Language::TypeScript, Language::Python]
+        );
+    }
+
+    #[tokio::test]
+    async fn status_reports_modified_and_deleted_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let files: Vec<RenderedFile> = ["a.txt", "b.txt", "c.txt"]
+            .iter()
+            .map(|name| RenderedFile {
+                dest: name.to_string(),
+                content: name.as_bytes().to_vec(),
+            })
+            .collect();
+        for file in &files {
+            std::fs::write(tmp.path().join(&file.dest), &file.content).unwrap();
+        }
+        let lock = lock_for(&files);
+
+        std::fs::write(tmp.path().join("b.txt"), "edited").unwrap();
+        std::fs::remove_file(tmp.path().join("c.txt")).unwrap();
+
+        let states: Vec<(String, FileState)> = lock
+            .status(tmp.path())
+            .await
+            .into_iter()
+            .map(|s| (s.path, s.state))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ("a.txt".to_string(), FileState::Unchanged),
+                ("b.txt".to_string(), FileState::Modified),
+                ("c.txt".to_string(), FileState::Deleted),
+            ]
+        );
+    }
+}
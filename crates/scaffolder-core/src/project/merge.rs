@@ -0,0 +1,278 @@
+//! Line-based three-way merge used when re-applying a template to a project
+
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+
+/// Labels written on conflict markers
+#[derive(Debug, Clone, Copy)]
+pub struct MergeLabels<'a> {
+    /// Label for the user's version (e.g. "local")
+    pub ours: &'a str,
+    /// Label for the original template version
+    pub base: &'a str,
+    /// Label for the new template version
+    pub theirs: &'a str,
+}
+
+/// Result of merging one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeResult {
+    /// Merged content, with conflict markers where both sides changed the same lines
+    pub content: String,
+    /// Number of conflicting regions
+    pub conflicts: usize,
+}
+
+/// A change one side made to a contiguous range of base lines
+#[derive(Debug, Clone)]
+struct Hunk<'a> {
+    /// Start of the replaced base range
+    start: usize,
+    /// End (exclusive) of the replaced base range
+    end: usize,
+    /// Lines replacing the base range
+    lines: Vec<&'a str>,
+}
+
+/// Merge `ours` and `theirs`, both derived from `base`
+///
+/// Non-overlapping changes from either side are applied; identical changes on both
+/// sides are applied once; anything else becomes a diff3-style conflict block.
+pub fn merge3(base: &str, ours: &str, theirs: &str, labels: MergeLabels<'_>) -> MergeResult {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let our_lines: Vec<&str> = ours.split_inclusive('\n').collect();
+    let their_lines: Vec<&str> = theirs.split_inclusive('\n').collect();
+
+    let our_hunks = hunks(&base_lines, &our_lines);
+    let their_hunks = hunks(&base_lines, &their_lines);
+
+    // Tag each hunk with its side and walk them in base order
+    let mut all: Vec<(bool, &Hunk)> = our_hunks
+        .iter()
+        .map(|h| (true, h))
+        .chain(their_hunks.iter().map(|h| (false, h)))
+        .collect();
+    all.sort_by_key(|(is_ours, h)| (h.start, h.end, !is_ours));
+
+    let mut output = String::with_capacity(ours.len().max(theirs.len()));
+    let mut conflicts = 0;
+    let mut position = 0;
+    let mut index = 0;
+
+    while index < all.len() {
+        // Group hunks whose base ranges overlap or touch
+        let group_start = all[index].1.start;
+        let mut group_end = all[index].1.end;
+        let mut group_len = 1;
+        while index + group_len < all.len() && all[index + group_len].1.start <= group_end {
+            group_end = group_end.max(all[index + group_len].1.end);
+            group_len += 1;
+        }
+        let group = &all[index..index + group_len];
+        index += group_len;
+
+        for line in &base_lines[position..group_start] {
+            output.push_str(line);
+        }
+        position = group_end;
+
+        let has_ours = group.iter().any(|(is_ours, _)| *is_ours);
+        let has_theirs = group.iter().any(|(is_ours, _)| !*is_ours);
+        let side = |want_ours: bool| -> Vec<&str> {
+            let side_hunks: Vec<&Hunk> = group
+                .iter()
+                .filter(|(is_ours, _)| *is_ours == want_ours)
+                .map(|(_, h)| *h)
+                .collect();
+            apply_hunks(&base_lines, group_start, group_end, &side_hunks)
+        };
+
+        let merged = match (has_ours, has_theirs) {
+            (true, false) => side(true),
+            (false, true) => side(false),
+            _ => {
+                let ours_side = side(true);
+                let theirs_side = side(false);
+                if ours_side == theirs_side {
+                    ours_side
+                } else {
+                    conflicts += 1;
+                    push_conflict(
+                        &mut output,
+                        &ours_side,
+                        &base_lines[group_start..group_end],
+                        &theirs_side,
+                        labels,
+                    );
+                    continue;
+                }
+            }
+        };
+
+        for line in merged {
+            output.push_str(line);
+        }
+    }
+
+    for line in &base_lines[position..] {
+        output.push_str(line);
+    }
+
+    MergeResult {
+        content: output,
+        conflicts,
+    }
+}
+
+/// Diff one side against the base, merging adjacent edits into single hunks
+fn hunks<'a>(base: &[&str], other: &[&'a str]) -> Vec<Hunk<'a>> {
+    let mut result: Vec<Hunk<'a>> = Vec::new();
+
+    for op in capture_diff_slices(Algorithm::Myers, base, other) {
+        let (start, end, new_range) = match op {
+            DiffOp::Equal { .. } => continue,
+            DiffOp::Delete {
+                old_index,
+                old_len,
+                new_index,
+            } => (old_index, old_index + old_len, new_index..new_index),
+            DiffOp::Insert {
+                old_index,
+                new_index,
+                new_len,
+            } => (old_index, old_index, new_index..new_index + new_len),
+            DiffOp::Replace {
+                old_index,
+                old_len,
+                new_index,
+                new_len,
+            } => (
+                old_index,
+                old_index + old_len,
+                new_index..new_index + new_len,
+            ),
+        };
+
+        match result.last_mut() {
+            Some(last) if last.end == start => {
+                last.end = end;
+                last.lines.extend_from_slice(&other[new_range]);
+            }
+            _ => result.push(Hunk {
+                start,
+                end,
+                lines: other[new_range].to_vec(),
+            }),
+        }
+    }
+
+    result
+}
+
+/// Apply one side's hunks to the base range `[start, end)`
+fn apply_hunks<'a>(
+    base: &[&'a str],
+    start: usize,
+    end: usize,
+    hunks: &[&Hunk<'a>],
+) -> Vec<&'a str> {
+    let mut lines = Vec::new();
+    let mut position = start;
+    for hunk in hunks {
+        lines.extend_from_slice(&base[position..hunk.start]);
+        lines.extend_from_slice(&hunk.lines);
+        position = hunk.end;
+    }
+    lines.extend_from_slice(&base[position..end]);
+    lines
+}
+
+/// Write a diff3-style conflict block
+fn push_conflict(
+    output: &mut String,
+    ours: &[&str],
+    base: &[&str],
+    theirs: &[&str],
+    labels: MergeLabels<'_>,
+) {
+    let mut push_section = |marker: String, lines: &[&str]| {
+        output.push_str(&marker);
+        output.push('\n');
+        for line in lines {
+            output.push_str(line);
+        }
+        if lines.last().is_some_and(|l| !l.ends_with('\n')) {
+            output.push('\n');
+        }
+    };
+
+    push_section(format!("<<<<<<< {}", labels.ours), ours);
+    push_section(format!("||||||| {}", labels.base), base);
+    push_section("=======".to_string(), theirs);
+    output.push_str(&format!(">>>>>>> {}\n", labels.theirs));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LABELS: MergeLabels<'static> = MergeLabels {
+        ours: "local",
+        base: "template 0.1.0",
+        theirs: "template 0.2.0",
+    };
+
+    #[test]
+    fn takes_changes_from_both_sides() {
+        let base = "a\nb\nc\nd\ne\n";
+        let ours = "a\nB\nc\nd\ne\n";
+        let theirs = "a\nb\nc\nd\nE\n";
+
+        let result = merge3(base, ours, theirs, LABELS);
+        assert_eq!(result.conflicts, 0);
+        assert_eq!(result.content, "a\nB\nc\nd\nE\n");
+    }
+
+    #[test]
+    fn identical_changes_apply_once() {
+        let base = "a\nb\nc\n";
+        let changed = "a\nb2\nc\nextra\n";
+
+        let result = merge3(base, changed, changed, LABELS);
+        assert_eq!(result.conflicts, 0);
+        assert_eq!(result.content, changed);
+    }
+
+    #[test]
+    fn conflicting_changes_get_markers() {
+        let base = "port: 3000\nhost: localhost\n";
+        let ours = "port: 4000\nhost: localhost\n";
+        let theirs = "port: 3111\nhost: localhost\n";
+
+        let result = merge3(base, ours, theirs, LABELS);
+        assert_eq!(result.conflicts, 1);
+        assert_eq!(
+            result.content,
+            "<<<<<<< local\nport: 4000\n||||||| template 0.1.0\nport: 3000\n=======\nport: 3111\n>>>>>>> template 0.2.0\nhost: localhost\n"
+        );
+    }
+
+    #[test]
+    fn insertions_at_end_are_kept() {
+        let base = "a\nb\n";
+        let ours = "a\nb\nlocal\n";
+        let theirs = "a2\nb\n";
+
+        let result = merge3(base, ours, theirs, LABELS);
+        assert_eq!(result.conflicts, 0);
+        assert_eq!(result.content, "a2\nb\nlocal\n");
+    }
+
+    #[test]
+    fn missing_trailing_newline_in_conflict() {
+        let result = merge3("x", "y", "z", LABELS);
+        assert_eq!(result.conflicts, 1);
+        assert!(result
+            .content
+            .contains("<<<<<<< local\ny\n||||||| template 0.1.0\nx\n=======\nz\n>>>>>>>"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Generated project metadata and lifecycle
+//!
+//! This module provides:
+//! - Reading the project identity written to `.iii/project.ini`
+//! - The template snapshot kept in `.iii/base/` as the merge base for upgrades
+//! - The scaffold lockfile recording template version, selections and file hashes
+//! - Three-way merging and re-applying newer template versions
+//! - Staged project creation that rolls back on failure
+//! - Answers files that replay the choices made during `create`
+
+pub mod answers;
+pub mod lock;
+pub mod merge;
+pub mod staging;
+pub mod upgrade;
+
+use crate::templates::copier::RenderedFile;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Directory holding project metadata, relative to the project root
+pub const PROJECT_META_DIR: &str = ".iii";
+
+/// Pristine copy of the template files as last written, relative to the project root
+///
+/// `upgrade` merges local edits against it. It is meant to be committed with the project;
+/// when it is missing, `upgrade` fetches the recorded template version instead.
+pub const BASE_SNAPSHOT_DIR: &str = ".iii/base";
+
+/// Project identity recorded in `.iii/project.ini`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectInfo {
+    pub project_id: String,
+    pub project_name: String,
+    /// Template the project was created from
+    pub source: String,
+    /// Template version the project was created from or last upgraded to
+    pub version: Option<String>,
+}
+
+impl ProjectInfo {
+    /// Read `.iii/project.ini` from a project directory
+    pub async fn read(project_dir: &Path) -> Result<Self> {
+        let path = project_dir.join(PROJECT_META_DIR).join("project.ini");
+        let content = fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    /// Parse the `[project]` section of a project.ini
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut project_id = None;
+        let mut project_name = None;
+        let mut source = None;
+        let mut version = None;
+
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().to_string();
+            match key.trim() {
+                "project_id" => project_id = Some(value),
+                "project_name" => project_name = Some(value),
+                "source" => source = Some(value),
+                "version" => version = Some(value).filter(|v| !v.is_empty()),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            project_id: project_id.context("missing project_id")?,
+            project_name: project_name.context("missing project_name")?,
+            source: source.context("missing source")?,
+            version,
+        })
+    }
+}
+
+/// Replace the template snapshot with freshly rendered template files
+pub async fn write_base_snapshot(project_dir: &Path, files: &[RenderedFile]) -> Result<()> {
+    let snapshot_dir = project_dir.join(BASE_SNAPSHOT_DIR);
+    if snapshot_dir.exists() {
+        fs::remove_dir_all(&snapshot_dir)
+            .await
+            .with_context(|| format!("Failed to clear {}", snapshot_dir.display()))?;
+    }
+
+    for file in files {
+        let path = snapshot_dir.join(&file.dest);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        fs::write(&path, &file.content)
+            .await
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Read the snapshot copy of a template file, if one was recorded
+pub async fn read_base_file(project_dir: &Path, dest: &str) -> Option<Vec<u8>> {
+    fs::read(project_dir.join(BASE_SNAPSHOT_DIR).join(dest))
+        .await
+        .ok()
+}
+
+/// List every file in the template snapshot (relative paths with `/` separators)
+pub fn base_snapshot_files(project_dir: &Path) -> Vec<String> {
+    let snapshot_dir = project_dir.join(BASE_SNAPSHOT_DIR);
+    walkdir::WalkDir::new(&snapshot_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(&snapshot_dir).ok()?;
+            Some(relative_path_string(relative))
+        })
+        .collect()
+}
+
+/// Render a relative path with `/` separators, the form used in template manifests
+pub(crate) fn relative_path_string(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Resolve a user-supplied project directory against the current directory
+pub fn resolve_project_dir(directory: Option<&Path>) -> PathBuf {
+    let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    match directory {
+        Some(dir) if dir.is_absolute() => dir.to_path_buf(),
+        Some(dir) => current_dir.join(dir),
+        None => current_dir,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_project_ini_with_version() {
+        let info = ProjectInfo::parse(
+            "[project]\nproject_id=abc\nproject_name=my-app\nsource=quickstart\nversion=0.1.0\n",
+        )
+        .unwrap();
+        assert_eq!(info.project_id, "abc");
+        assert_eq!(info.project_name, "my-app");
+        assert_eq!(info.source, "quickstart");
+        assert_eq!(info.version.as_deref(), Some("0.1.0"));
+    }
+
+    #[test]
+    fn parse_project_ini_without_version() {
+        let info = ProjectInfo::parse(
+            "[project]\nproject_id=abc\nproject_name=my-app\nsource=quickstart\n",
+        )
+        .unwrap();
+        assert_eq!(info.version, None);
+        assert!(ProjectInfo::parse("[project]\nproject_id=abc\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn base_snapshot_round_trip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let files = vec![
+            RenderedFile {
+                dest: "README.md".to_string(),
+                content: b"# App\n".to_vec(),
+            },
+            RenderedFile {
+                dest: "workers/api/main.py".to_string(),
+                content: b"print()\n".to_vec(),
+            },
+        ];
+
+        write_base_snapshot(tmp.path(), &files).await.unwrap();
+        assert_eq!(
+            base_snapshot_files(tmp.path()),
+            vec!["README.md", "workers/api/main.py"]
+        );
+        assert_eq!(
+            read_base_file(tmp.path(), "README.md").await.unwrap(),
+            b"# App\n"
+        );
+
+        // A new snapshot replaces the previous one entirely
+        write_base_snapshot(tmp.path(), &files[..1]).await.unwrap();
+        assert_eq!(base_snapshot_files(tmp.path()), vec!["README.md"]);
+    }
+}
@@ -0,0 +1,428 @@
+//! Staged project creation with rollback
+//!
+//! Files are first written to a temporary directory next to the project, then moved
+//! into place in one step. Every change made to the project directory is journaled so
+//! a failure (or Ctrl+C) undoes exactly what this run did and nothing else. Directories
+//! the run created go as a whole, so whatever hooks and dependency installs put in them
+//! goes too. In directories that already existed, only the outputs the run claimed
+//! beforehand (`node_modules`, `.venv`, lockfiles) are removed; anything else that
+//! appears there meanwhile is left alone.
+//!
+//! Filesystem work here is synchronous so rollback can also run from a signal handler.
+
+use crate::templates::copier::RenderedFile;
+use crate::templates::manifest;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Journal of the staging run registered for Ctrl+C rollback
+static ACTIVE: Mutex<Option<Arc<Mutex<Journal>>>> = Mutex::new(None);
+
+/// Changes made to the project directory, in the order they were made
+#[derive(Debug, Default)]
+struct Journal {
+    /// Temporary directory holding staged files and replaced originals
+    stage_dir: PathBuf,
+    /// Paths later steps are expected to create, which did not exist when claimed
+    claimed: Vec<PathBuf>,
+    /// Directories created in the project, parents first
+    created_dirs: Vec<PathBuf>,
+    /// Files moved into the project
+    created_files: Vec<PathBuf>,
+    /// Existing files set aside before being replaced, as `(original, saved copy)`
+    replaced: Vec<(PathBuf, PathBuf)>,
+    /// Existing files renamed to backups, as `(original, backup)`
+    renamed: Vec<(PathBuf, PathBuf)>,
+    /// Set once the run is finished or rolled back
+    closed: bool,
+}
+
+impl Journal {
+    /// Undo every journaled change, returning how many created files were removed
+    fn rollback(&mut self) -> usize {
+        if self.closed {
+            return 0;
+        }
+        self.closed = true;
+
+        let mut removed = 0;
+        for file in self.created_files.iter().rev() {
+            if fs::remove_file(file).is_ok() {
+                removed += 1;
+            }
+        }
+        for (original, saved) in self.replaced.iter().rev() {
+            let _ = move_file(saved, original);
+        }
+        for (original, backup) in self.renamed.iter().rev() {
+            let _ = move_file(backup, original);
+        }
+        for path in self.claimed.iter().rev() {
+            let _ = match fs::symlink_metadata(path) {
+                Ok(meta) if meta.is_dir() => fs::remove_dir_all(path),
+                Ok(_) => fs::remove_file(path),
+                Err(_) => continue,
+            };
+        }
+        // Directories this run created hold nothing that was there before
+        for dir in self.created_dirs.iter().rev() {
+            let _ = fs::remove_dir_all(dir);
+        }
+        let _ = fs::remove_dir_all(&self.stage_dir);
+
+        removed
+    }
+
+    fn finish(&mut self) {
+        if !self.closed {
+            self.closed = true;
+            let _ = fs::remove_dir_all(&self.stage_dir);
+        }
+    }
+}
+
+/// A project being created through a staging directory
+///
+/// Dropping it without calling [`StagedProject::finish`] rolls the project back.
+pub struct StagedProject {
+    target_dir: PathBuf,
+    journal: Arc<Mutex<Journal>>,
+}
+
+impl StagedProject {
+    /// Create the staging directory next to `target_dir`
+    pub fn begin(target_dir: &Path) -> Result<Self> {
+        let name = target_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "project".to_string());
+        let parent = target_dir
+            .parent()
+            .filter(|p| p.is_dir())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(std::env::temp_dir);
+        let stage_dir = parent.join(format!(".{}.staging-{}", name, uuid::Uuid::new_v4()));
+
+        fs::create_dir_all(stage_dir.join("files")).with_context(|| {
+            format!(
+                "Failed to create staging directory: {}",
+                stage_dir.display()
+            )
+        })?;
+
+        let journal = Arc::new(Mutex::new(Journal {
+            stage_dir,
+            ..Default::default()
+        }));
+        *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = Some(journal.clone());
+
+        Ok(Self {
+            target_dir: target_dir.to_path_buf(),
+            journal,
+        })
+    }
+
+    /// Directory that mirrors the project layout; anything written here is committed
+    pub fn dir(&self) -> PathBuf {
+        self.lock().stage_dir.join("files")
+    }
+
+    /// Write rendered files into the staging directory
+    pub fn stage(&self, files: &[RenderedFile]) -> Result<()> {
+        let dir = self.dir();
+        for file in files {
+            if !manifest::is_project_relative(&file.dest) {
+                anyhow::bail!("Refusing to write outside the project: {}", file.dest);
+            }
+            let path = dir.join(&file.dest);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+            }
+            fs::write(&path, &file.content)
+                .with_context(|| format!("Failed to write file: {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Move staged files into the project, renaming `backups` (`(dest, backup)`) first
+    ///
+    /// Returns the project-relative paths of the committed files.
+    pub fn commit(&self, backups: &[(String, String)]) -> Result<Vec<String>> {
+        let staged_dir = self.dir();
+        let mut journal = self.lock();
+
+        create_dirs(&self.target_dir, &mut journal)?;
+
+        for (dest, backup) in backups {
+            let original = self.target_dir.join(dest);
+            let backup = self.target_dir.join(backup);
+            move_file(&original, &backup)
+                .with_context(|| format!("Failed to back up {}", original.display()))?;
+            journal.renamed.push((original, backup));
+        }
+
+        let mut committed = Vec::new();
+        let staged_files: Vec<PathBuf> = walkdir::WalkDir::new(&staged_dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect();
+
+        for staged in staged_files {
+            let relative = staged
+                .strip_prefix(&staged_dir)
+                .context("Staged file outside the staging directory")?
+                .to_path_buf();
+            let target = self.target_dir.join(&relative);
+
+            if target.exists() {
+                let saved = journal.stage_dir.join("replaced").join(&relative);
+                if let Some(parent) = saved.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                move_file(&target, &saved)
+                    .with_context(|| format!("Failed to replace {}", target.display()))?;
+                journal.replaced.push((target.clone(), saved));
+            } else if let Some(parent) = target.parent() {
+                create_dirs(parent, &mut journal)?;
+            }
+
+            move_file(&staged, &target)
+                .with_context(|| format!("Failed to write file: {}", target.display()))?;
+            journal.created_files.push(target);
+            committed.push(super::relative_path_string(&relative));
+        }
+
+        Ok(committed)
+    }
+
+    /// Journal paths that later steps (hooks, dependency installs) are about to create, so
+    /// rollback removes them; paths that already exist are left out
+    pub fn claim<I>(&self, paths: I)
+    where
+        I: IntoIterator<Item = PathBuf>,
+    {
+        let mut journal = self.lock();
+        for path in paths {
+            if fs::symlink_metadata(&path).is_err() && !journal.claimed.contains(&path) {
+                journal.claimed.push(path);
+            }
+        }
+    }
+
+    /// Undo everything this run changed in the project, returning how many files were removed
+    pub fn rollback(self) -> usize {
+        let removed = self.lock().rollback();
+        self.unregister();
+        removed
+    }
+
+    /// Keep the project as committed and remove the staging directory
+    pub fn finish(self) {
+        self.lock().finish();
+        self.unregister();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Journal> {
+        self.journal.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn unregister(&self) {
+        let mut active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+        if active
+            .as_ref()
+            .is_some_and(|j| Arc::ptr_eq(j, &self.journal))
+        {
+            *active = None;
+        }
+    }
+}
+
+impl Drop for StagedProject {
+    fn drop(&mut self) {
+        self.lock().rollback();
+        self.unregister();
+    }
+}
+
+/// Roll back the project currently being created, if any
+///
+/// Intended for Ctrl+C handlers, which exit without unwinding. Returns how many
+/// created files were removed.
+pub fn rollback_interrupted() -> usize {
+    let active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).take();
+    match active {
+        Some(journal) => journal.lock().unwrap_or_else(|e| e.into_inner()).rollback(),
+        None => 0,
+    }
+}
+
+/// Create `dir` and any missing parents, journaling each directory created
+fn create_dirs(dir: &Path, journal: &mut Journal) -> Result<()> {
+    let missing: Vec<&Path> = dir.ancestors().take_while(|d| !d.exists()).collect();
+    for dir in missing.into_iter().rev() {
+        fs::create_dir(dir)
+            .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        journal.created_dirs.push(dir.to_path_buf());
+    }
+    Ok(())
+}
+
+/// Rename a file, falling back to copy and delete across filesystems
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rendered(dest: &str, content: &str) -> RenderedFile {
+        RenderedFile {
+            dest: dest.to_string(),
+            content: content.as_bytes().to_vec(),
+        }
+    }
+
+    fn staging_dirs(parent: &Path) -> usize {
+        fs::read_dir(parent)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().contains(".staging-"))
+            .count()
+    }
+
+    #[test]
+    fn commit_moves_staged_files_into_place() {
+        let tmp = tempfile::tempdir().unwrap();
+        let target = tmp.path().join("app");
+
+        let staged = StagedProject::begin(&target).unwrap();
+        staged
+            .stage(&[
+                rendered("README.md", "# App\n"),
+                rendered("src/main.ts", "x"),
+            ])
+            .unwrap();
+        assert!(!target.exists());
+
+        let committed = staged.commit(&[]).unwrap();
+        staged.finish();
+
+        assert_eq!(committed, vec!["README.md", "src/main.ts"]);
+        assert_eq!(fs::read_to_string(target.join("src/main.ts")).unwrap(), "x");
+        assert_eq!(staging_dirs(tmp.path()), 0);
+    }
+
+    #[test]
+    fn rollback_removes_only_files_from_this_run() {
+        let tmp = tempfile::tempdir().unwrap();
+        let target = tmp.path().join("app");
+        fs::create_dir_all(target.join("src")).unwrap();
+        fs::write(target.join("notes.txt"), "mine").unwrap();
+        fs::write(target.join("README.md"), "original").unwrap();
+        fs::write(target.join(".gitignore"), "old").unwrap();
+
+        let staged = StagedProject::begin(&target).unwrap();
+        staged
+            .stage(&[
+                rendered("README.md", "template"),
+                rendered("src/main.ts", "x"),
+                rendered("workers/api/main.py", "y"),
+                rendered(".gitignore", "new"),
+            ])
+            .unwrap();
+        staged
+            .commit(&[(".gitignore".to_string(), ".gitignore.bak".to_string())])
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(target.join("README.md")).unwrap(),
+            "template"
+        );
+
+        assert_eq!(staged.rollback(), 4);
+
+        assert_eq!(
+            fs::read_to_string(target.join("notes.txt")).unwrap(),
+            "mine"
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("README.md")).unwrap(),
+            "original"
+        );
+        assert_eq!(
+            fs::read_to_string(target.join(".gitignore")).unwrap(),
+            "old"
+        );
+        assert!(!target.join(".gitignore.bak").exists());
+        assert!(!target.join("src/main.ts").exists());
+        assert!(target.join("src").exists());
+        assert!(!target.join("workers").exists());
+        assert_eq!(staging_dirs(tmp.path()), 0);
+    }
+
+    #[test]
+    fn rollback_removes_what_finish_steps_created() {
+        let tmp = tempfile::tempdir().unwrap();
+        let target = tmp.path().join("app");
+        fs::create_dir_all(target.join("src")).unwrap();
+        fs::write(target.join("notes.txt"), "mine").unwrap();
+
+        let staged = StagedProject::begin(&target).unwrap();
+        staged
+            .stage(&[
+                rendered("package.json", "{}"),
+                rendered("workers/api/main.py", "y"),
+            ])
+            .unwrap();
+        staged.commit(&[]).unwrap();
+        staged.claim([
+            target.join("node_modules"),
+            target.join("package-lock.json"),
+            target.join("src"),
+        ]);
+        // What a dependency install and a hook leave behind
+        fs::create_dir_all(target.join("node_modules/zod")).unwrap();
+        fs::write(target.join("node_modules/zod/index.js"), "").unwrap();
+        fs::write(target.join("package-lock.json"), "{}").unwrap();
+        fs::create_dir_all(target.join("workers/api/.venv/bin")).unwrap();
+        // Created by the user while the install ran
+        fs::write(target.join("draft.md"), "mine too").unwrap();
+        fs::write(target.join("src/todo.ts"), "").unwrap();
+
+        staged.rollback();
+
+        let mut left: Vec<String> = fs::read_dir(&target)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        left.sort();
+        assert_eq!(left, vec!["draft.md", "notes.txt", "src"]);
+        assert!(target.join("src/todo.ts").exists());
+    }
+
+    #[test]
+    fn dropping_without_finish_rolls_back() {
+        let tmp = tempfile::tempdir().unwrap();
+        let target = tmp.path().join("app");
+
+        {
+            let staged = StagedProject::begin(&target).unwrap();
+            staged.stage(&[rendered("README.md", "x")]).unwrap();
+            staged.commit(&[]).unwrap();
+            fs::create_dir_all(target.join("node_modules/zod")).unwrap();
+        }
+
+        assert!(!target.exists());
+        assert_eq!(staging_dirs(tmp.path()), 0);
+    }
+}
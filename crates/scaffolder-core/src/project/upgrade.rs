@@ -0,0 +1,407 @@
+//! Re-applying a newer template version to an existing project
+
+use super::merge::{merge3, MergeLabels};
+use super::{base_snapshot_files, read_base_file, write_base_snapshot};
+use crate::runtime::check::Language;
+use crate::templates::copier::RenderedFile;
+use crate::templates::manifest::{
+    path_matches, FileLanguage, LanguageFiles, OptionValues, TemplateManifest,
+};
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::Path;
+use tokio::fs;
+
+/// What happened to a single file during an upgrade
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileOutcome {
+    /// New in this template version, written to the project
+    Added,
+    /// Project file already matches the new template version
+    Unchanged,
+    /// Not modified locally, replaced with the new template version
+    Updated,
+    /// Modified locally and unchanged in the template, local version kept
+    KeptLocal,
+    /// Local and template changes merged cleanly
+    Merged,
+    /// Local and template changes overlap; written with conflict markers
+    Conflict,
+    /// Binary file changed on both sides; local version kept
+    BinaryConflict,
+    /// Deleted locally, the new template version was not restored
+    SkippedDeleted,
+    /// Dropped from the template and unmodified locally, removed from the project
+    Removed,
+    /// Dropped from the template but modified locally, local version kept
+    KeptRemoved,
+}
+
+impl FileOutcome {
+    /// Short description used in upgrade reports
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileOutcome::Added => "added",
+            FileOutcome::Unchanged => "unchanged",
+            FileOutcome::Updated => "updated",
+            FileOutcome::KeptLocal => "kept local changes",
+            FileOutcome::Merged => "merged",
+            FileOutcome::Conflict => "conflict",
+            FileOutcome::BinaryConflict => "binary conflict, kept local",
+            FileOutcome::SkippedDeleted => "deleted locally, skipped",
+            FileOutcome::Removed => "removed",
+            FileOutcome::KeptRemoved => "removed from template, kept local",
+        }
+    }
+
+    /// Whether the user needs to look at this file
+    pub fn needs_attention(&self) -> bool {
+        matches!(self, FileOutcome::Conflict | FileOutcome::BinaryConflict)
+    }
+}
+
+/// A file touched (or deliberately left alone) by an upgrade
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    /// Path relative to the project root
+    pub path: String,
+    pub outcome: FileOutcome,
+}
+
+/// Per-file results of an upgrade
+#[derive(Debug, Clone, Default)]
+pub struct UpgradeReport {
+    pub changes: Vec<FileChange>,
+}
+
+impl UpgradeReport {
+    /// Files left with conflicts for the user to resolve
+    pub fn conflicts(&self) -> Vec<&FileChange> {
+        self.changes
+            .iter()
+            .filter(|c| c.outcome.needs_attention())
+            .collect()
+    }
+
+    /// Number of files with the given outcome
+    pub fn count(&self, outcome: FileOutcome) -> usize {
+        self.changes.iter().filter(|c| c.outcome == outcome).count()
+    }
+}
+
+/// Three-way merge freshly rendered template files into a project
+///
+/// The snapshot in `.iii/base/` is the merge base, the project files are the local
+/// side and `new_files` is the incoming template version. Once every file is handled
+/// the snapshot is replaced with `new_files`.
+pub async fn upgrade_project(
+    project_dir: &Path,
+    new_files: &[RenderedFile],
+    labels: MergeLabels<'_>,
+) -> Result<UpgradeReport> {
+    let mut report = UpgradeReport::default();
+
+    for file in new_files {
+        let outcome = upgrade_file(project_dir, file, labels).await?;
+        report.changes.push(FileChange {
+            path: file.dest.clone(),
+            outcome,
+        });
+    }
+
+    // Files that existed in the old template version but not in the new one
+    let new_paths: HashSet<&str> = new_files.iter().map(|f| f.dest.as_str()).collect();
+    for path in base_snapshot_files(project_dir) {
+        if new_paths.contains(path.as_str()) {
+            continue;
+        }
+        let target = project_dir.join(&path);
+        let Ok(local) = fs::read(&target).await else {
+            continue;
+        };
+        let base = read_base_file(project_dir, &path).await;
+        let outcome = if base.as_deref() == Some(local.as_slice()) {
+            fs::remove_file(&target)
+                .await
+                .with_context(|| format!("Failed to remove {}", target.display()))?;
+            FileOutcome::Removed
+        } else {
+            FileOutcome::KeptRemoved
+        };
+        report.changes.push(FileChange { path, outcome });
+    }
+
+    write_base_snapshot(project_dir, new_files).await?;
+
+    Ok(report)
+}
+
+async fn upgrade_file(
+    project_dir: &Path,
+    file: &RenderedFile,
+    labels: MergeLabels<'_>,
+) -> Result<FileOutcome> {
+    let target = project_dir.join(&file.dest);
+    let local = fs::read(&target).await.ok();
+    let base = read_base_file(project_dir, &file.dest).await;
+
+    let Some(local) = local else {
+        if base.is_some() {
+            return Ok(FileOutcome::SkippedDeleted);
+        }
+        write_file(&target, &file.content).await?;
+        return Ok(FileOutcome::Added);
+    };
+
+    if local == file.content {
+        return Ok(FileOutcome::Unchanged);
+    }
+    // A file that exists locally but was never part of the template merges against an empty base
+    let base = base.unwrap_or_default();
+    if local == base {
+        write_file(&target, &file.content).await?;
+        return Ok(FileOutcome::Updated);
+    }
+    if base == file.content {
+        return Ok(FileOutcome::KeptLocal);
+    }
+
+    let (Some(base), Some(ours), Some(theirs)) =
+        (as_text(&base), as_text(&local), as_text(&file.content))
+    else {
+        return Ok(FileOutcome::BinaryConflict);
+    };
+
+    let merged = merge3(base, ours, theirs, labels);
+    write_file(&target, merged.content.as_bytes()).await?;
+    Ok(if merged.conflicts == 0 {
+        FileOutcome::Merged
+    } else {
+        FileOutcome::Conflict
+    })
+}
+
+/// Treat content as text only if it is valid UTF-8 without NUL bytes
+fn as_text(content: &[u8]) -> Option<&str> {
+    if content.contains(&0) {
+        return None;
+    }
+    std::str::from_utf8(content).ok()
+}
+
+async fn write_file(path: &Path, content: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    fs::write(path, content)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Work out which languages a project was created with from its template snapshot
+///
+/// Required languages are always included; other languages are detected from the
+/// language-specific files present in the snapshot.
+pub fn infer_languages(
+    snapshot_files: &[String],
+    manifest: &TemplateManifest,
+    language_files: &LanguageFiles,
+) -> Vec<Language> {
+    let detected: HashSet<FileLanguage> = snapshot_files
+        .iter()
+        .filter_map(|path| language_files.get_language_for_file(path))
+        .collect();
+
+    [
+        ("typescript", Language::TypeScript, FileLanguage::TypeScript),
+        ("javascript", Language::JavaScript, FileLanguage::JavaScript),
+        ("python", Language::Python, FileLanguage::Python),
+        ("rust", Language::Rust, FileLanguage::Rust),
+    ]
+    .into_iter()
+    .filter(|(name, _, file_language)| {
+        manifest.is_required(name) || detected.contains(file_language)
+    })
+    .map(|(_, language, _)| language)
+    .collect()
+}
+
+/// Work out a project's option values from its template snapshot
+///
+/// An option that gates files is on when any of those files is in the snapshot;
+/// options that only gate content blocks fall back to their default.
+pub fn infer_options(snapshot_files: &[String], manifest: &TemplateManifest) -> OptionValues {
+    manifest
+        .options
+        .iter()
+        .map(|option| {
+            let value = if option.files.is_empty() {
+                option.default
+            } else {
+                snapshot_files
+                    .iter()
+                    .any(|path| option.files.iter().any(|p| path_matches(path, p)))
+            };
+            (option.name.clone(), value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LABELS: MergeLabels<'static> = MergeLabels {
+        ours: "local",
+        base: "template 0.1.0",
+        theirs: "template 0.2.0",
+    };
+
+    fn rendered(dest: &str, content: &str) -> RenderedFile {
+        RenderedFile {
+            dest: dest.to_string(),
+            content: content.as_bytes().to_vec(),
+        }
+    }
+
+    async fn project_from(files: &[RenderedFile]) -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        for file in files {
+            write_file(&tmp.path().join(&file.dest), &file.content)
+                .await
+                .unwrap();
+        }
+        write_base_snapshot(tmp.path(), files).await.unwrap();
+        tmp
+    }
+
+    fn outcome(report: &UpgradeReport, path: &str) -> FileOutcome {
+        report
+            .changes
+            .iter()
+            .find(|c| c.path == path)
+            .map(|c| c.outcome)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn upgrade_updates_untouched_files_and_merges_edits() {
+        let tmp = project_from(&[
+            rendered("README.md", "# App\n"),
+            rendered("config.yaml", "port: 3000\nhost: localhost\nlog: info\n"),
+        ])
+        .await;
+        std::fs::write(
+            tmp.path().join("config.yaml"),
+            "port: 4000\nhost: localhost\nlog: info\n",
+        )
+        .unwrap();
+
+        let report = upgrade_project(
+            tmp.path(),
+            &[
+                rendered("README.md", "# App v2\n"),
+                rendered("config.yaml", "port: 3000\nhost: localhost\nlog: debug\n"),
+                rendered("new.txt", "new\n"),
+            ],
+            LABELS,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(outcome(&report, "README.md"), FileOutcome::Updated);
+        assert_eq!(outcome(&report, "config.yaml"), FileOutcome::Merged);
+        assert_eq!(outcome(&report, "new.txt"), FileOutcome::Added);
+        assert!(report.conflicts().is_empty());
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("config.yaml")).unwrap(),
+            "port: 4000\nhost: localhost\nlog: debug\n"
+        );
+        // The snapshot now tracks the new template version
+        assert_eq!(
+            read_base_file(tmp.path(), "README.md").await.unwrap(),
+            b"# App v2\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn upgrade_reports_conflicts_with_markers() {
+        let tmp = project_from(&[rendered("config.yaml", "port: 3000\n")]).await;
+        std::fs::write(tmp.path().join("config.yaml"), "port: 4000\n").unwrap();
+
+        let report = upgrade_project(
+            tmp.path(),
+            &[rendered("config.yaml", "port: 3111\n")],
+            LABELS,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(outcome(&report, "config.yaml"), FileOutcome::Conflict);
+        assert_eq!(report.conflicts().len(), 1);
+        let content = std::fs::read_to_string(tmp.path().join("config.yaml")).unwrap();
+        assert!(content.contains("<<<<<<< local\nport: 4000\n"));
+        assert!(content.contains(">>>>>>> template 0.2.0\n"));
+    }
+
+    #[tokio::test]
+    async fn upgrade_respects_local_deletions_and_upstream_removals() {
+        let tmp = project_from(&[
+            rendered("deleted.txt", "a\n"),
+            rendered("dropped.txt", "b\n"),
+            rendered("dropped-edited.txt", "c\n"),
+        ])
+        .await;
+        std::fs::remove_file(tmp.path().join("deleted.txt")).unwrap();
+        std::fs::write(tmp.path().join("dropped-edited.txt"), "c, edited\n").unwrap();
+
+        let report = upgrade_project(tmp.path(), &[rendered("deleted.txt", "a2\n")], LABELS)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome(&report, "deleted.txt"), FileOutcome::SkippedDeleted);
+        assert_eq!(outcome(&report, "dropped.txt"), FileOutcome::Removed);
+        assert_eq!(
+            outcome(&report, "dropped-edited.txt"),
+            FileOutcome::KeptRemoved
+        );
+        assert!(!tmp.path().join("deleted.txt").exists());
+        assert!(!tmp.path().join("dropped.txt").exists());
+        assert!(tmp.path().join("dropped-edited.txt").exists());
+    }
+
+    #[test]
+    fn infer_languages_from_snapshot() {
+        let manifest: TemplateManifest = serde_yaml::from_str(
+            "name: T\ndescription: T\nversion: '0.1.0'\nrequires: [typescript]\noptional: [python, rust]\nfiles: []\n",
+        )
+        .unwrap();
+        let language_files = LanguageFiles {
+            python: vec!["*.py".to_string()],
+            rust: vec!["*.rs".to_string()],
+            ..Default::default()
+        };
+
+        let languages = infer_languages(
+            &["workers/api/main.py".to_string(), "README.md".to_string()],
+            &manifest,
+            &language_files,
+        );
+        assert_eq!(languages, vec![Language::TypeScript, Language::Python]);
+    }
+
+    #[test]
+    fn infer_options_from_gated_files() {
+        let manifest: TemplateManifest = serde_yaml::from_str(
+            "name: T\ndescription: T\nversion: '0.1.0'\nfiles: []\noptions:\n  - name: docker\n    files: [Dockerfile]\n  - name: redis\n    default: true\n    files: [redis.yaml]\n  - name: verbose\n    default: true\n",
+        )
+        .unwrap();
+
+        let options = infer_options(&["Dockerfile".to_string()], &manifest);
+        assert_eq!(options.get("docker"), Some(&true));
+        assert_eq!(options.get("redis"), Some(&false));
+        assert_eq!(options.get("verbose"), Some(&true));
+    }
+}
@@ -0,0 +1,304 @@
+//! Interactive decisions made while creating a project
+//!
+//! The `create` flow asks a [`Prompter`] whenever it needs a choice it was not given on the
+//! command line. `TerminalPrompter` asks on the terminal; [`AnswersPrompter`] answers from
+//! an answers file so the same flow runs without a TTY.
+
+use crate::project::answers::Answers;
+use crate::runtime::check::Language;
+use crate::runtime::tool::ToolConfig;
+use crate::templates::copier::ConflictResolution;
+use crate::templates::manifest::{TemplateManifest, TemplateOption, TemplateVariable};
+use anyhow::Result;
+use std::path::Path;
+
+/// What to do about a required tool that is not installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolAction {
+    Install,
+    /// Open the tool's documentation and stop
+    OpenDocs,
+    Skip,
+}
+
+/// Answer for one file that already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConflictChoice {
+    pub resolution: ConflictResolution,
+    /// Use the same resolution for the remaining conflicts without asking
+    pub apply_to_all: bool,
+}
+
+/// A yes/no question
+#[derive(Debug, Clone, Copy)]
+pub enum Confirmation<'a> {
+    /// Run the install command of a missing tool
+    InstallTool { tool: &'a str, command: &'a str },
+    /// Carry on after a tool failed to install
+    ContinueWithoutTool { tool: &'a str },
+    /// Create the project in a directory that already has entries
+    NonEmptyDirectory { path: &'a Path, entries: usize },
+    /// Run the template's post-create hooks
+    RunHooks { count: usize },
+}
+
+impl Confirmation<'_> {
+    /// The question as shown to the user
+    pub fn message(&self) -> String {
+        match self {
+            Confirmation::InstallTool { .. } => "Proceed with installation?".to_string(),
+            Confirmation::ContinueWithoutTool { tool } => format!("Continue without {}?", tool),
+            Confirmation::NonEmptyDirectory { .. } => "Continue anyway?".to_string(),
+            Confirmation::RunHooks { .. } => "Run these commands now?".to_string(),
+        }
+    }
+
+    /// The preselected answer; running template hooks is never assumed
+    pub fn default_answer(&self) -> bool {
+        !matches!(
+            self,
+            Confirmation::ContinueWithoutTool { .. } | Confirmation::RunHooks { .. }
+        )
+    }
+}
+
+/// Source of the choices `create` asks for
+pub trait Prompter {
+    /// A tool the product requires is missing
+    fn tool_action(&mut self, tool: &ToolConfig, install_command: &str) -> Result<ToolAction>;
+
+    fn confirm(&mut self, confirmation: Confirmation<'_>) -> Result<bool>;
+
+    /// Pick one of several templates, returning its index
+    fn select_template(&mut self, templates: &[(String, TemplateManifest)]) -> Result<usize>;
+
+    /// Project directory as typed, relative to the working directory unless absolute
+    fn project_directory(&mut self) -> Result<String>;
+
+    /// Optional languages to add to the template's required ones
+    fn select_languages(
+        &mut self,
+        prompt: &str,
+        selectable: &[Language],
+        preselected: &[Language],
+    ) -> Result<Vec<Language>>;
+
+    /// Names of the options to enable among those not set on the command line
+    fn select_options(&mut self, options: &[&TemplateOption]) -> Result<Vec<String>>;
+
+    /// Value for a template variable not set on the command line
+    fn input_variable(&mut self, variable: &TemplateVariable) -> Result<String>;
+
+    /// Decide what to do with an existing file the template would overwrite
+    fn resolve_conflict(
+        &mut self,
+        path: &str,
+        existing: &[u8],
+        new: &[u8],
+    ) -> Result<ConflictChoice>;
+}
+
+/// Answers every question from an answers file, falling back to the defaults a prompt
+/// would preselect
+///
+/// Missing tools are skipped and existing files are an error, since neither can be
+/// decided safely without a person.
+#[derive(Debug, Clone, Default)]
+pub struct AnswersPrompter {
+    answers: Answers,
+}
+
+impl AnswersPrompter {
+    pub fn new(answers: Answers) -> Self {
+        Self { answers }
+    }
+}
+
+impl Prompter for AnswersPrompter {
+    fn tool_action(&mut self, _tool: &ToolConfig, _install_command: &str) -> Result<ToolAction> {
+        Ok(ToolAction::Skip)
+    }
+
+    fn confirm(&mut self, confirmation: Confirmation<'_>) -> Result<bool> {
+        Ok(confirmation.default_answer())
+    }
+
+    fn select_template(&mut self, templates: &[(String, TemplateManifest)]) -> Result<usize> {
+        let names: Vec<&str> = templates.iter().map(|(name, _)| name.as_str()).collect();
+        let Some(template) = &self.answers.template else {
+            anyhow::bail!(
+                "The answers do not name a template. Available templates: {}",
+                names.join(", ")
+            );
+        };
+        names
+            .iter()
+            .position(|name| name == template)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Template '{}' not found. Available templates: {}",
+                    template,
+                    names.join(", ")
+                )
+            })
+    }
+
+    fn project_directory(&mut self) -> Result<String> {
+        Ok(self
+            .answers
+            .directory
+            .as_ref()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_else(|| ".".to_string()))
+    }
+
+    fn select_languages(
+        &mut self,
+        _prompt: &str,
+        selectable: &[Language],
+        preselected: &[Language],
+    ) -> Result<Vec<Language>> {
+        let Some(names) = &self.answers.languages else {
+            return Ok(preselected.to_vec());
+        };
+        Ok(names
+            .iter()
+            .filter_map(|name| Language::parse(name))
+            .filter(|lang| selectable.contains(lang))
+            .collect())
+    }
+
+    fn select_options(&mut self, options: &[&TemplateOption]) -> Result<Vec<String>> {
+        Ok(options
+            .iter()
+            .filter(|option| {
+                self.answers
+                    .options
+                    .get(&option.name)
+                    .copied()
+                    .unwrap_or(option.default)
+            })
+            .map(|option| option.name.clone())
+            .collect())
+    }
+
+    fn input_variable(&mut self, variable: &TemplateVariable) -> Result<String> {
+        self.answers
+            .variables
+            .get(&variable.name)
+            .or(variable.default.as_ref())
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("The answers do not set variable '{}'", variable.name))
+    }
+
+    fn resolve_conflict(
+        &mut self,
+        path: &str,
+        _existing: &[u8],
+        _new: &[u8],
+    ) -> Result<ConflictChoice> {
+        anyhow::bail!(
+            "{} already exists; choose a conflict policy to run without prompts",
+            path
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn manifest(name: &str) -> TemplateManifest {
+        serde_yaml::from_str(&format!(
+            "name: {}\ndescription: test\nversion: '0.1.0'\n",
+            name
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn answers_prompter_answers_from_the_file() {
+        let mut prompter = AnswersPrompter::new(Answers {
+            template: Some("api".to_string()),
+            directory: Some(PathBuf::from("apps/api")),
+            languages: Some(vec!["py".to_string(), "rust".to_string()]),
+            options: [("docker".to_string(), false)].into(),
+            variables: [("service".to_string(), "billing".to_string())].into(),
+            allow_hooks: false,
+        });
+        let templates = vec![
+            ("web".to_string(), manifest("Web")),
+            ("api".to_string(), manifest("API")),
+        ];
+        let docker = TemplateOption {
+            name: "docker".to_string(),
+            description: String::new(),
+            default: true,
+            files: Vec::new(),
+        };
+        let redis = TemplateOption {
+            name: "redis".to_string(),
+            default: true,
+            ..docker.clone()
+        };
+
+        assert_eq!(prompter.select_template(&templates).unwrap(), 1);
+        assert_eq!(prompter.project_directory().unwrap(), "apps/api");
+        assert_eq!(
+            prompter
+                .select_languages("", &[Language::Python, Language::TypeScript], &[])
+                .unwrap(),
+            vec![Language::Python]
+        );
+        assert_eq!(
+            prompter.select_options(&[&docker, &redis]).unwrap(),
+            vec!["redis"]
+        );
+        assert!(prompter
+            .confirm(Confirmation::NonEmptyDirectory {
+                path: Path::new("."),
+                entries: 3
+            })
+            .unwrap());
+        let variable = |name: &str, default: Option<&str>| TemplateVariable {
+            name: name.to_string(),
+            description: String::new(),
+            default: default.map(String::from),
+        };
+        assert_eq!(
+            prompter.input_variable(&variable("service", None)).unwrap(),
+            "billing"
+        );
+        assert_eq!(
+            prompter
+                .input_variable(&variable("port", Some("3000")))
+                .unwrap(),
+            "3000"
+        );
+        assert!(prompter.input_variable(&variable("region", None)).is_err());
+        assert!(prompter.resolve_conflict("README.md", b"", b"").is_err());
+    }
+
+    #[test]
+    fn answers_prompter_falls_back_to_defaults() {
+        let mut prompter = AnswersPrompter::default();
+
+        assert!(prompter
+            .select_template(&[("web".to_string(), manifest("Web"))])
+            .is_err());
+        assert_eq!(prompter.project_directory().unwrap(), ".");
+        assert_eq!(
+            prompter
+                .select_languages("", &[Language::Python], &[Language::Python])
+                .unwrap(),
+            vec![Language::Python]
+        );
+        assert!(!prompter
+            .confirm(Confirmation::ContinueWithoutTool { tool: "iii" })
+            .unwrap());
+        assert!(!prompter
+            .confirm(Confirmation::RunHooks { count: 1 })
+            .unwrap());
+    }
+}
@@ -14,6 +14,17 @@ pub enum Language {
 }
 
 impl Language {
+    /// Parse a language name or short alias (e.g. "typescript", "ts", "py")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "typescript" | "ts" => Some(Language::TypeScript),
+            "javascript" | "js" => Some(Language::JavaScript),
+            "python" | "py" => Some(Language::Python),
+            "rust" | "rs" => Some(Language::Rust),
+            _ => None,
+        }
+    }
+
     pub fn display_name(&self) -> &'static str {
         match self {
             Language::TypeScript => "TypeScript",
@@ -31,7 +42,7 @@ impl fmt::Display for Language {
 }
 
 /// Runtime detection result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct RuntimeInfo {
     pub name: &'static str,
     pub version: Option<String>,
@@ -152,8 +163,7 @@ pub fn check_runtimes_with_advisory(
             .iter()
             .filter(|l| matches!(l, Language::TypeScript | Language::JavaScript))
             .collect();
-        let js_advisory =
-            !js_ts_langs.is_empty() && js_ts_langs.iter().all(|l| is_advisory(l));
+        let js_advisory = !js_ts_langs.is_empty() && js_ts_langs.iter().all(|l| is_advisory(l));
 
         let any_js_available = bun.available || node.available;
         if bun.available {
@@ -3,12 +3,13 @@
 //! Provides a reusable abstraction for checking and installing CLI tools
 //! like iii, or any other tool that can be installed via a shell script.
 
+use crate::events::{Event, EventSink};
 use anyhow::Result;
 use colored::Colorize;
 use std::process::Stdio;
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command as TokioCommand;
+use tokio::process::{Child, Command as TokioCommand};
 use tokio::time::timeout;
 
 /// Timeout for installation (30 seconds)
@@ -75,54 +76,32 @@ impl ToolManager {
     }
 
     /// Install the tool using its official install script
-    /// Shows the command being executed and streams output
-    pub async fn install(&self) -> Result<()> {
+    /// Reports the command being executed and its output as events
+    pub async fn install(&self, events: &dyn EventSink) -> Result<()> {
         let cmd = self.install_command();
-        println!();
-        println!("{} {}", "Running:".dimmed(), cmd.yellow());
-        println!();
+        events.emit(Event::ToolInstallStarted {
+            tool: self.config.display_name.to_string(),
+            command: cmd.clone(),
+        });
+
+        let result = self.run_install(&cmd, events).await;
+        events.emit(Event::ToolInstallFinished {
+            tool: self.config.display_name.to_string(),
+            error: result.as_ref().err().map(|e| format!("{:#}", e)),
+        });
+        result
+    }
 
+    async fn run_install(&self, cmd: &str, events: &dyn EventSink) -> Result<()> {
         // Create the command
         let mut child = TokioCommand::new("sh")
             .arg("-c")
-            .arg(&cmd)
+            .arg(cmd)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
 
-        // Get stdout and stderr
-        let stdout = child.stdout.take().expect("Failed to capture stdout");
-        let stderr = child.stderr.take().expect("Failed to capture stderr");
-
-        let mut stdout_reader = BufReader::new(stdout).lines();
-        let mut stderr_reader = BufReader::new(stderr).lines();
-
-        // Stream output with timeout
-        let output_task = async {
-            loop {
-                tokio::select! {
-                    line = stdout_reader.next_line() => {
-                        match line {
-                            Ok(Some(line)) => println!("  {}", line),
-                            Ok(None) => break,
-                            Err(e) => {
-                                eprintln!("{} {}", "Error reading stdout:".red(), e);
-                                break;
-                            }
-                        }
-                    }
-                    line = stderr_reader.next_line() => {
-                        match line {
-                            Ok(Some(line)) => eprintln!("  {}", line.yellow()),
-                            Ok(None) => {}
-                            Err(e) => {
-                                eprintln!("{} {}", "Error reading stderr:".red(), e);
-                            }
-                        }
-                    }
-                }
-            }
-        };
+        let output_task = stream_output(&mut child, events);
 
         // Wait for output with timeout
         match timeout(INSTALL_TIMEOUT, output_task).await {
@@ -130,7 +109,6 @@ impl ToolManager {
             Err(_) => {
                 // Kill the process on timeout
                 let _ = child.kill().await;
-                println!();
                 anyhow::bail!(
                     "Installation timed out after {} seconds.\n\
                      The server may be unreachable. Please try again later or install manually:\n\
@@ -144,7 +122,6 @@ impl ToolManager {
         // Wait for process to complete with timeout
         match timeout(Duration::from_secs(5), child.wait()).await {
             Ok(Ok(status)) => {
-                println!();
                 if status.success() {
                     Ok(())
                 } else {
@@ -173,13 +150,65 @@ impl ToolManager {
     pub fn open_docs(&self) -> Result<()> {
         println!(
             "{}",
-            format!("Opening {} documentation in your browser...", self.config.display_name).cyan()
+            format!(
+                "Opening {} documentation in your browser...",
+                self.config.display_name
+            )
+            .cyan()
         );
         open::that(self.config.docs_url)?;
         Ok(())
     }
 }
 
+/// Send a child's stdout and stderr lines as [`Event::ProcessOutput`] until both pipes close
+///
+/// The child must have been spawned with piped stdout and stderr.
+pub async fn stream_output(child: &mut Child, events: &dyn EventSink) {
+    let output = |line: String, stderr: bool| events.emit(Event::ProcessOutput { line, stderr });
+
+    // Get stdout and stderr
+    let stdout = child.stdout.take().expect("Failed to capture stdout");
+    let stderr = child.stderr.take().expect("Failed to capture stderr");
+
+    let mut stdout_reader = BufReader::new(stdout).lines();
+    let mut stderr_reader = BufReader::new(stderr).lines();
+    let mut stderr_open = true;
+
+    loop {
+        tokio::select! {
+            line = stdout_reader.next_line() => {
+                match line {
+                    Ok(Some(line)) => output(line, false),
+                    Ok(None) => break,
+                    Err(e) => {
+                        output(format!("Error reading stdout: {}", e), true);
+                        break;
+                    }
+                }
+            }
+            line = stderr_reader.next_line(), if stderr_open => {
+                match line {
+                    Ok(Some(line)) => output(line, true),
+                    Ok(None) => stderr_open = false,
+                    Err(e) => {
+                        output(format!("Error reading stderr: {}", e), true);
+                        stderr_open = false;
+                    }
+                }
+            }
+        }
+    }
+
+    // Drain whatever stderr is left once stdout has closed
+    while stderr_open {
+        match stderr_reader.next_line().await {
+            Ok(Some(line)) => output(line, true),
+            _ => stderr_open = false,
+        }
+    }
+}
+
 /// Pre-configured tool manager for iii
 pub fn iii_tool() -> ToolManager {
     ToolManager::new(ToolConfig {
@@ -5,7 +5,11 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use tokio::fs;
 
+use crate::config::PackageManager;
+use crate::events::{Event, EventSink};
 use crate::runtime::check::Language;
+use crate::runtime::tool::stream_output;
+use crate::templates::worker::WORKERS_DIR;
 
 const API_KEY: &str = "a7182ac460dde671c8f2e1318b517228";
 const AMPLITUDE_ENDPOINT: &str = "https://api2.amplitude.com/2/httpapi";
@@ -229,7 +233,14 @@ async fn send_amplitude(
     tools_version: &str,
     event_properties: serde_json::Value,
 ) {
-    send_amplitude_to(&resolve_endpoint(), event_type, platform, tools_version, event_properties).await;
+    send_amplitude_to(
+        &resolve_endpoint(),
+        event_type,
+        platform,
+        tools_version,
+        event_properties,
+    )
+    .await;
 }
 
 pub fn spawn_project_event(
@@ -242,13 +253,7 @@ pub fn spawn_project_event(
         return None;
     }
     Some(tokio::spawn(async move {
-        send_amplitude(
-            event_type,
-            platform,
-            &tools_version,
-            event_properties,
-        )
-        .await;
+        send_amplitude(event_type, platform, &tools_version, event_properties).await;
     }))
 }
 
@@ -264,72 +269,168 @@ pub async fn write_project_ini(
     project_id: &str,
     project_name: &str,
     template: &str,
+    template_version: &str,
 ) -> Result<()> {
     let dir = project_dir.join(".iii");
     fs::create_dir_all(&dir)
         .await
         .context("create .iii directory")?;
-    let body = format!("[project]\nproject_id={project_id}\nproject_name={project_name}\nsource={template}\n");
+    let body = format!("[project]\nproject_id={project_id}\nproject_name={project_name}\nsource={template}\nversion={template_version}\n");
     fs::write(dir.join("project.ini"), body)
         .await
         .context("write project.ini")?;
     Ok(())
 }
 
-pub async fn run_dependency_install(project_dir: &Path, langs: &[Language]) -> Result<()> {
+/// Outcome of installing dependencies in one directory of a new project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyInstall {
+    /// Directory relative to the project root (`.` for the root itself)
+    pub directory: String,
+    /// The last install command that was tried
+    pub command: String,
+    pub success: bool,
+}
+
+/// Run an install command in `dir`, sending its output as events
+async fn run_install_command(
+    dir: &Path,
+    command: &str,
+    events: &dyn EventSink,
+) -> Result<std::process::ExitStatus> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().context("empty install command")?;
+    let mut child = tokio::process::Command::new(program)
+        .args(parts)
+        .current_dir(dir)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .with_context(|| format!("spawn {}", command))?;
+    stream_output(&mut child, events).await;
+    child
+        .wait()
+        .await
+        .with_context(|| format!("wait for {}", command))
+}
+
+/// Install dependencies for the selected languages in the project root and each worker
+/// directory, and report what ran in each
+///
+/// A failing JavaScript install is an error; Python installers are tried in turn
+/// (`uv sync`, then `pip`/`pip3`) and a failure is only reported.
+pub async fn run_dependency_install(
+    project_dir: &Path,
+    langs: &[Language],
+    package_manager: PackageManager,
+    events: &dyn EventSink,
+) -> Result<Vec<DependencyInstall>> {
+    events.emit(Event::DependencyInstallStarted {
+        project_dir: project_dir.to_path_buf(),
+    });
+    let result = install_dependencies(project_dir, langs, package_manager, events).await;
+    events.emit(Event::DependencyInstallFinished {
+        installs: result.as_ref().cloned().unwrap_or_default(),
+        error: result.as_ref().err().map(|e| format!("{:#}", e)),
+    });
+    result
+}
+
+async fn install_dependencies(
+    project_dir: &Path,
+    langs: &[Language],
+    package_manager: PackageManager,
+    events: &dyn EventSink,
+) -> Result<Vec<DependencyInstall>> {
+    let mut installs = Vec::new();
+    for directory in install_directories(project_dir) {
+        let dir = project_dir.join(&directory);
+        let installed = install_in(&dir, langs, package_manager, events)
+            .await
+            .with_context(|| format!("install dependencies in {}", directory))?;
+        if let Some((command, success)) = installed {
+            installs.push(DependencyInstall {
+                directory,
+                command,
+                success,
+            });
+        }
+    }
+    Ok(installs)
+}
+
+/// What dependency installs create in a directory: installed packages, virtualenvs
+/// and lockfiles
+pub(crate) const INSTALL_OUTPUTS: &[&str] = &[
+    "node_modules",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "bun.lockb",
+    "bun.lock",
+    ".venv",
+    "uv.lock",
+];
+
+/// The project root (`.`) followed by each worker directory, relative to the root
+pub(crate) fn install_directories(project_dir: &Path) -> Vec<String> {
+    let mut workers: Vec<String> = std::fs::read_dir(project_dir.join(WORKERS_DIR))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| format!("{}/{}", WORKERS_DIR, entry.file_name().to_string_lossy()))
+        .collect();
+    workers.sort();
+    std::iter::once(".".to_string()).chain(workers).collect()
+}
+
+/// Install dependencies in one directory, returning the last command tried and whether it
+/// succeeded; `None` when the directory has no manifest for the selected languages
+async fn install_in(
+    dir: &Path,
+    langs: &[Language],
+    package_manager: PackageManager,
+    events: &dyn EventSink,
+) -> Result<Option<(String, bool)>> {
     let has_js_ts = langs
         .iter()
         .any(|l| matches!(l, Language::TypeScript | Language::JavaScript));
-    if has_js_ts && project_dir.join("package.json").exists() {
-        let status = tokio::process::Command::new("npm")
-            .args(["install"])
-            .current_dir(project_dir)
-            .status()
-            .await
-            .context("spawn npm install")?;
+    if has_js_ts && dir.join("package.json").exists() {
+        let command = format!("{} install", package_manager.name());
+        let status = run_install_command(dir, &command, events).await?;
         if !status.success() {
-            anyhow::bail!("npm install exited with status {}", status);
+            anyhow::bail!("{} exited with status {}", command, status);
         }
-        return Ok(());
+        return Ok(Some((command, true)));
     }
 
     let has_python = langs.contains(&Language::Python);
-    if has_python && project_dir.join("pyproject.toml").exists() {
-        let uv = tokio::process::Command::new("uv")
-            .args(["sync"])
-            .current_dir(project_dir)
-            .status()
-            .await;
-        if let Ok(s) = uv {
-            if s.success() {
-                return Ok(());
-            }
+    let mut last_attempt = None;
+    if has_python && dir.join("pyproject.toml").exists() {
+        if is_success(run_install_command(dir, "uv sync", events).await) {
+            return Ok(Some(("uv sync".to_string(), true)));
         }
+        last_attempt = Some("uv sync");
     }
-    if has_python && project_dir.join("requirements.txt").exists() {
-        let pip = tokio::process::Command::new("pip")
-            .args(["install", "-r", "requirements.txt"])
-            .current_dir(project_dir)
-            .status()
-            .await;
-        if let Ok(s) = pip {
-            if s.success() {
-                return Ok(());
-            }
-        }
-        let pip3 = tokio::process::Command::new("pip3")
-            .args(["install", "-r", "requirements.txt"])
-            .current_dir(project_dir)
-            .status()
-            .await;
-        if let Ok(s) = pip3 {
-            if s.success() {
-                return Ok(());
+    if has_python && dir.join("requirements.txt").exists() {
+        for command in [
+            "pip install -r requirements.txt",
+            "pip3 install -r requirements.txt",
+        ] {
+            if is_success(run_install_command(dir, command, events).await) {
+                return Ok(Some((command.to_string(), true)));
             }
+            last_attempt = Some(command);
         }
     }
 
-    Ok(())
+    Ok(last_attempt.map(|command| (command.to_string(), false)))
+}
+
+/// A Python installer that is missing counts as failed, so the next one is tried
+fn is_success(status: Result<std::process::ExitStatus>) -> bool {
+    status.is_ok_and(|s| s.success())
 }
 
 #[cfg(test)]
@@ -349,10 +450,37 @@ mod tests {
         assert!(s.contains("source=quickstart"));
     }
 
+    #[tokio::test]
+    async fn dependency_install_reports_nothing_without_manifests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let installs = run_dependency_install(
+            tmp.path(),
+            &[Language::TypeScript, Language::Python],
+            PackageManager::default(),
+            &crate::events::NoEvents,
+        )
+        .await
+        .unwrap();
+        assert!(installs.is_empty());
+    }
+
+    #[test]
+    fn dependency_installs_cover_the_root_and_each_worker() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("workers/math-worker")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("workers/caller-worker")).unwrap();
+        std::fs::write(tmp.path().join("workers/README.md"), "").unwrap();
+
+        assert_eq!(
+            install_directories(tmp.path()),
+            vec![".", "workers/caller-worker", "workers/math-worker"]
+        );
+    }
+
     #[tokio::test]
     async fn write_project_ini_includes_source() {
         let tmp = tempfile::tempdir().unwrap();
-        write_project_ini(tmp.path(), "proj-1", "my-proj", "quickstart")
+        write_project_ini(tmp.path(), "proj-1", "my-proj", "quickstart", "0.1.0")
             .await
             .unwrap();
         let contents =
@@ -360,14 +488,21 @@ mod tests {
         assert!(contents.contains("project_id=proj-1"));
         assert!(contents.contains("project_name=my-proj"));
         assert!(contents.contains("source=quickstart"));
+        assert!(contents.contains("version=0.1.0"));
     }
 
     #[tokio::test]
     async fn write_project_ini_with_custom_template() {
         let tmp = tempfile::tempdir().unwrap();
-        write_project_ini(tmp.path(), "proj-2", "other", "multi-worker-orchestration")
-            .await
-            .unwrap();
+        write_project_ini(
+            tmp.path(),
+            "proj-2",
+            "other",
+            "multi-worker-orchestration",
+            "0.2.0",
+        )
+        .await
+        .unwrap();
         let contents =
             std::fs::read_to_string(tmp.path().join(".iii").join("project.ini")).unwrap();
         assert!(contents.contains("source=multi-worker-orchestration"));
@@ -395,7 +530,9 @@ mod tests {
 
         // Point HOME at a temp dir so telemetry.yaml won't exist
         let tmp = tempfile::tempdir().unwrap();
-        unsafe { std::env::set_var("HOME", tmp.path()); }
+        unsafe {
+            std::env::set_var("HOME", tmp.path());
+        }
 
         send_amplitude_to(
             &endpoint,
@@ -406,7 +543,9 @@ mod tests {
         )
         .await;
 
-        unsafe { std::env::remove_var("HOME"); }
+        unsafe {
+            std::env::remove_var("HOME");
+        }
 
         let requests: Vec<Request> = mock_server.received_requests().await.unwrap();
         assert_eq!(requests.len(), 1);
@@ -414,7 +553,10 @@ mod tests {
         let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
         let event = &body["events"][0];
         assert_eq!(event["event_type"], "iii_tools_telemetry_failed");
-        assert_eq!(event["event_properties"]["reason"], "telemetry_yaml_missing");
+        assert_eq!(
+            event["event_properties"]["reason"],
+            "telemetry_yaml_missing"
+        );
     }
 
     #[tokio::test]
@@ -437,7 +579,9 @@ mod tests {
         )
         .unwrap();
 
-        unsafe { std::env::set_var("HOME", tmp.path()); }
+        unsafe {
+            std::env::set_var("HOME", tmp.path());
+        }
 
         let endpoint = format!("{}/2/httpapi", mock_server.uri());
 
@@ -455,7 +599,9 @@ mod tests {
         )
         .await;
 
-        unsafe { std::env::remove_var("HOME"); }
+        unsafe {
+            std::env::remove_var("HOME");
+        }
 
         let requests: Vec<Request> = mock_server.received_requests().await.unwrap();
         assert_eq!(requests.len(), 1);
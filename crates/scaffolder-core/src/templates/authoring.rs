@@ -0,0 +1,460 @@
+//! Helpers for writing template manifests and registering templates in a registry
+
+use super::manifest::{LanguageFiles, RootManifest};
+use crate::runtime::check::Language;
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+
+/// Contents of a new template.yaml
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestDraft {
+    pub name: String,
+    pub description: String,
+    pub version: String,
+    pub min_iii_version: Option<String>,
+    pub requires: Vec<String>,
+    pub optional: Vec<String>,
+    /// File names the registry's `language_files` don't classify, listed as template `common`
+    pub common_files: Vec<String>,
+    pub files: Vec<String>,
+    pub next_steps: Vec<String>,
+}
+
+impl ManifestDraft {
+    /// Render the draft in the layout used by the bundled templates
+    pub fn render(&self) -> String {
+        let mut yaml = String::new();
+        let _ = writeln!(yaml, "name: {}", yaml_string(&self.name));
+        let _ = writeln!(yaml, "description: {}", yaml_string(&self.description));
+        let _ = writeln!(yaml, "version: '{}'", self.version);
+        if let Some(min) = &self.min_iii_version {
+            let _ = writeln!(yaml, "min_iii_version: '{}'", min);
+        }
+        yaml.push('\n');
+
+        write_list(&mut yaml, "requires", &self.requires, "");
+        write_list(&mut yaml, "optional", &self.optional, "");
+        yaml.push('\n');
+
+        if !self.common_files.is_empty() {
+            let _ = writeln!(
+                yaml,
+                "# Files the registry's language_files don't classify; always copied"
+            );
+            let _ = writeln!(yaml, "language_files:");
+            write_list(&mut yaml, "common", &self.common_files, "  ");
+            yaml.push('\n');
+        }
+
+        let _ = writeln!(
+            yaml,
+            "# Explicit list of files to copy (shared_files from root are added automatically)"
+        );
+        write_list(&mut yaml, "files", &self.files, "");
+
+        if !self.next_steps.is_empty() {
+            yaml.push('\n');
+            write_list(&mut yaml, "next_steps", &self.next_steps, "");
+        }
+        yaml
+    }
+}
+
+/// Languages that have a starter worker
+pub const STARTER_LANGUAGES: &[Language] =
+    &[Language::TypeScript, Language::JavaScript, Language::Python];
+
+// Starter worker sources; `{name}`, `{language}` and `{entry}` are substituted in paths and content
+const NODE_WORKER_YAML: &str = r#"name: {name}
+runtime:
+  language: {language}
+  package_manager: npm
+  entry: {entry}
+scripts:
+  install: 'npm install'
+  start: 'npm run dev'
+"#;
+
+const TS_PACKAGE_JSON: &str = r#"{
+  "name": "{name}",
+  "version": "0.1.0",
+  "type": "module",
+  "scripts": {
+    "dev": "tsx watch src/worker.ts",
+    "build": "tsc"
+  },
+  "dependencies": {
+    "iii-sdk": "latest"
+  },
+  "devDependencies": {
+    "@types/node": "^25.2.2",
+    "tsx": "^4.0.0",
+    "typescript": "^5.0.0"
+  }
+}
+"#;
+
+const JS_PACKAGE_JSON: &str = r#"{
+  "name": "{name}",
+  "version": "0.1.0",
+  "type": "module",
+  "scripts": {
+    "dev": "node --watch src/worker.js"
+  },
+  "dependencies": {
+    "iii-sdk": "latest"
+  }
+}
+"#;
+
+const TS_CONFIG: &str = r#"{
+  "compilerOptions": {
+    "target": "ES2022",
+    "module": "ESNext",
+    "moduleResolution": "bundler",
+    "esModuleInterop": true,
+    "strict": true,
+    "skipLibCheck": true,
+    "outDir": "./dist"
+  },
+  "include": ["src/**/*"],
+  "exclude": ["node_modules", "dist"]
+}
+"#;
+
+const TS_WORKER: &str = r#"import { registerWorker, Logger } from 'iii-sdk';
+
+const iii = registerWorker(process.env.III_URL ?? 'ws://localhost:49134', {
+  workerName: '{name}',
+});
+
+const logger = new Logger();
+
+iii.registerFunction('{name}::hello', async (payload: { name?: string }) => {
+  logger.info('{name}::hello called', payload);
+  return { message: `Hello, ${payload.name ?? 'world'}!` };
+});
+"#;
+
+const JS_WORKER: &str = r#"import { registerWorker, Logger } from 'iii-sdk';
+
+const iii = registerWorker(process.env.III_URL ?? 'ws://localhost:49134', {
+  workerName: '{name}',
+});
+
+const logger = new Logger();
+
+iii.registerFunction('{name}::hello', async (payload) => {
+  logger.info('{name}::hello called', payload);
+  return { message: `Hello, ${payload.name ?? 'world'}!` };
+});
+"#;
+
+const PY_WORKER_YAML: &str = r#"name: {name}
+runtime:
+  language: python
+  package_manager: pip
+  entry: {entry}
+scripts:
+  install: "pip install -r requirements.txt"
+  start: "watchfiles 'python {entry}'"
+"#;
+
+const PY_REQUIREMENTS: &str = "iii-sdk\nwatchfiles\n";
+
+const PY_WORKER: &str = r#"import os
+from iii import register_worker, InitOptions, Logger
+
+iii = register_worker(
+    os.environ.get("III_URL", "ws://localhost:49134"),
+    InitOptions(worker_name="{name}"),
+)
+logger = Logger()
+
+
+def hello(payload: dict) -> dict:
+    logger.info("{name}::hello called")
+    return {"message": f"Hello, {payload.get('name', 'world')}!"}
+
+
+iii.register_function("{name}::hello", hello)
+"#;
+
+/// Files of a minimal worker registering one `<name>::hello` function, as `(path, content)`
+///
+/// Paths are relative to the template root (`workers/<name>/...`). Returns `None` for
+/// languages without a starter.
+pub fn starter_worker(language: Language, name: &str) -> Option<Vec<(String, String)>> {
+    let (entry, sources) = match language {
+        Language::TypeScript => (
+            "src/worker.ts".to_string(),
+            vec![
+                ("iii.worker.yaml", NODE_WORKER_YAML),
+                ("package.json", TS_PACKAGE_JSON),
+                ("tsconfig.json", TS_CONFIG),
+                ("{entry}", TS_WORKER),
+            ],
+        ),
+        Language::JavaScript => (
+            "src/worker.js".to_string(),
+            vec![
+                ("iii.worker.yaml", NODE_WORKER_YAML),
+                ("package.json", JS_PACKAGE_JSON),
+                ("{entry}", JS_WORKER),
+            ],
+        ),
+        Language::Python => (
+            format!("{}.py", name.replace('-', "_")),
+            vec![
+                ("iii.worker.yaml", PY_WORKER_YAML),
+                ("requirements.txt", PY_REQUIREMENTS),
+                ("{entry}", PY_WORKER),
+            ],
+        ),
+        Language::Rust => return None,
+    };
+
+    let language_name = language.display_name().to_lowercase();
+    let substitute = |text: &str| {
+        text.replace("{name}", name)
+            .replace("{language}", &language_name)
+            .replace("{entry}", &entry)
+    };
+    Some(
+        sources
+            .into_iter()
+            .map(|(path, source)| {
+                (
+                    format!("workers/{}/{}", name, substitute(path)),
+                    substitute(source),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// File names among `files` that no `language_files` pattern classifies, sorted and unique
+pub fn unclassified_files(files: &[String], language_files: &LanguageFiles) -> Vec<String> {
+    let mut names: Vec<String> = files
+        .iter()
+        .filter(|file| language_files.get_language_for_file(file).is_none())
+        .map(|file| file.rsplit('/').next().unwrap_or(file).to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn write_list(yaml: &mut String, key: &str, items: &[String], indent: &str) {
+    if items.is_empty() {
+        let _ = writeln!(yaml, "{}{}: []", indent, key);
+        return;
+    }
+    let _ = writeln!(yaml, "{}{}:", indent, key);
+    for item in items {
+        let _ = writeln!(yaml, "{}  - {}", indent, yaml_string(item));
+    }
+}
+
+/// Quote a scalar when YAML would otherwise misread it
+pub(crate) fn yaml_string(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '.' | '_' | '-' | ' '))
+        && !value.starts_with(['-', '.', ' '])
+        && !value.ends_with(' ')
+        && matches!(serde_yaml::from_str(value), Ok(serde_yaml::Value::String(parsed)) if parsed == value);
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+}
+
+/// Add a template to the root manifest's `templates` list, keeping comments and layout
+///
+/// A commented-out entry for the template (`# - name`) is uncommented in place; otherwise
+/// the entry is appended after the last active one. Returns the content unchanged when the
+/// template is already listed.
+pub fn register_template(root_yaml: &str, template: &str) -> Result<String> {
+    let root: RootManifest =
+        serde_yaml::from_str(root_yaml).context("Failed to parse root manifest")?;
+    if root.templates.iter().any(|t| t == template) {
+        return Ok(root_yaml.to_string());
+    }
+
+    let mut lines: Vec<String> = root_yaml.lines().map(str::to_string).collect();
+    let key = lines
+        .iter()
+        .position(|l| l.starts_with("templates:"))
+        .context("Root manifest has no top-level templates list")?;
+    let inline = lines[key]["templates:".len()..].trim().to_string();
+
+    if inline.starts_with('[') {
+        // Flow style (`templates: [a, b]`): rewrite as a block list
+        let mut entries = vec!["templates:".to_string()];
+        entries.extend(root.templates.iter().map(|t| format!("  - {}", t)));
+        entries.push(format!("  - {}", template));
+        lines.splice(key..=key, entries);
+    } else {
+        // Entries and comments belonging to the list, up to the next top-level key
+        let end = lines[key + 1..]
+            .iter()
+            .position(|l| {
+                !(l.trim().is_empty() || l.starts_with([' ', '\t', '-']) || l.starts_with('#'))
+            })
+            .map(|i| key + 1 + i)
+            .unwrap_or(lines.len());
+        let block = key + 1..end;
+
+        let entry = |line: &str| {
+            let trimmed = line.trim_start();
+            trimmed
+                .strip_prefix("- ")
+                .map(|value| (line.len() - trimmed.len(), value.trim().to_string()))
+        };
+        let commented = |line: &str| {
+            let trimmed = line.trim_start();
+            let rest = trimmed.strip_prefix('#')?.trim_start();
+            let value = rest.strip_prefix("- ")?.trim();
+            Some((line.len() - trimmed.len(), value.to_string()))
+        };
+
+        let indent = block
+            .clone()
+            .find_map(|i| entry(&lines[i]).map(|(indent, _)| indent))
+            .unwrap_or(2);
+        let new_entry = format!("{}- {}", " ".repeat(indent), template);
+
+        if let Some(i) = block
+            .clone()
+            .find(|&i| commented(&lines[i]).is_some_and(|(_, value)| value == template))
+        {
+            lines[i] = new_entry;
+        } else {
+            let insert_at = block
+                .clone()
+                .rfind(|&i| entry(&lines[i]).is_some())
+                .map(|i| i + 1)
+                .unwrap_or(key + 1);
+            lines.insert(insert_at, new_entry);
+        }
+    }
+
+    let mut updated = lines.join("\n");
+    if root_yaml.ends_with('\n') {
+        updated.push('\n');
+    }
+
+    let parsed: RootManifest = serde_yaml::from_str(&updated)
+        .context("Registering the template produced an invalid root manifest")?;
+    if !parsed.templates.iter().any(|t| t == template) {
+        anyhow::bail!(
+            "Could not add '{}' to the root manifest's templates list",
+            template
+        );
+    }
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_appends_after_last_entry() {
+        let root = "# Root\ntemplates:\n  - quickstart\n  # - starter\n\nshared_files: []\n";
+        assert_eq!(
+            register_template(root, "api").unwrap(),
+            "# Root\ntemplates:\n  - quickstart\n  - api\n  # - starter\n\nshared_files: []\n"
+        );
+    }
+
+    #[test]
+    fn register_uncomments_existing_entry() {
+        let root = "templates:\n  - quickstart\n  # - multi-worker\n  # - starter\n";
+        assert_eq!(
+            register_template(root, "starter").unwrap(),
+            "templates:\n  - quickstart\n  # - multi-worker\n  - starter\n"
+        );
+    }
+
+    #[test]
+    fn register_handles_flow_and_empty_lists() {
+        assert_eq!(
+            register_template("templates: [a, b]\n", "c").unwrap(),
+            "templates:\n  - a\n  - b\n  - c\n"
+        );
+        assert_eq!(
+            register_template("templates: []\nlanguage_files: {}\n", "c").unwrap(),
+            "templates:\n  - c\nlanguage_files: {}\n"
+        );
+    }
+
+    #[test]
+    fn register_is_a_no_op_when_listed() {
+        let root = "templates:\n- a\n";
+        assert_eq!(register_template(root, "a").unwrap(), root);
+        assert_eq!(
+            register_template(root, "b").unwrap(),
+            "templates:\n- a\n- b\n"
+        );
+    }
+
+    #[test]
+    fn rendered_draft_parses_as_manifest() {
+        let draft = ManifestDraft {
+            name: "My API".to_string(),
+            description: "Says: hello".to_string(),
+            version: "0.1.0".to_string(),
+            min_iii_version: Some("0.11.0".to_string()),
+            requires: vec!["typescript".to_string()],
+            optional: vec![],
+            common_files: vec!["Dockerfile".to_string()],
+            files: vec!["README.md".to_string(), ".env.example".to_string()],
+            next_steps: vec!["Run: iii".to_string()],
+        };
+        let manifest: super::super::TemplateManifest =
+            serde_yaml::from_str(&draft.render()).unwrap();
+
+        assert_eq!(manifest.name, "My API");
+        assert_eq!(manifest.description, "Says: hello");
+        assert_eq!(manifest.min_iii_version.as_deref(), Some("0.11.0"));
+        assert_eq!(manifest.language_files.common, vec!["Dockerfile"]);
+        assert_eq!(manifest.files.len(), 2);
+        assert_eq!(manifest.next_steps, vec!["Run: iii"]);
+    }
+
+    #[test]
+    fn starter_workers_are_valid() {
+        for &language in STARTER_LANGUAGES {
+            let files = starter_worker(language, "hello-worker").unwrap();
+            for (path, content) in &files {
+                assert!(path.starts_with("workers/hello-worker/"), "{}", path);
+                if path.ends_with(".json") {
+                    serde_json::from_str::<serde_json::Value>(content)
+                        .unwrap_or_else(|e| panic!("{}: {}", path, e));
+                }
+                if path.ends_with(".yaml") {
+                    let worker: serde_yaml::Value = serde_yaml::from_str(content).unwrap();
+                    assert_eq!(worker["name"], "hello-worker");
+                }
+            }
+        }
+
+        let python = starter_worker(Language::Python, "hello-worker").unwrap();
+        assert!(python
+            .iter()
+            .any(|(path, _)| path == "workers/hello-worker/hello_worker.py"));
+        assert!(starter_worker(Language::Rust, "x").is_none());
+    }
+
+    #[test]
+    fn yaml_strings_are_quoted_when_needed() {
+        assert_eq!(yaml_string("src/index.ts"), "src/index.ts");
+        assert_eq!(yaml_string(".env.example"), "'.env.example'");
+        assert_eq!(yaml_string("true"), "'true'");
+        assert_eq!(yaml_string("it's: here"), "'it''s: here'");
+    }
+}
@@ -0,0 +1,186 @@
+//! Listing the templates available in a registry
+
+use super::fetcher::TemplateFetcher;
+use super::manifest::TemplateManifest;
+use crate::product::ProductConfig;
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Metadata about one template, as shown by `list`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateSummary {
+    /// Template directory name, as passed to `--template`
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub version: String,
+    pub min_iii_version: Option<String>,
+    /// Languages that are always included
+    pub requires: Vec<String>,
+    /// Languages the user can add
+    pub optional: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+impl TemplateSummary {
+    pub fn new(id: &str, manifest: &TemplateManifest) -> Self {
+        Self {
+            id: id.to_string(),
+            name: manifest.name.clone(),
+            description: manifest.description.clone(),
+            version: manifest.version.clone(),
+            min_iii_version: manifest.min_iii_version.clone(),
+            requires: manifest.requires.clone(),
+            optional: manifest.optional.clone(),
+            tags: manifest.tags.clone(),
+        }
+    }
+}
+
+/// Templates in a registry, plus the ones whose manifest could not be loaded
+#[derive(Debug, Default)]
+pub struct Catalog {
+    pub templates: Vec<TemplateSummary>,
+    /// Template ids that failed to load, with the error message
+    pub unavailable: Vec<(String, String)>,
+}
+
+/// Load every template listed in the registry's root manifest
+pub async fn fetch_catalog(fetcher: &mut TemplateFetcher) -> Result<Catalog> {
+    let root_manifest = fetcher.fetch_root_manifest().await?;
+    let mut catalog = Catalog::default();
+
+    for id in &root_manifest.templates {
+        match fetcher.fetch_template_manifest(id).await {
+            Ok(manifest) => catalog.templates.push(TemplateSummary::new(id, &manifest)),
+            Err(e) => catalog.unavailable.push((id.clone(), format!("{:#}", e))),
+        }
+    }
+
+    Ok(catalog)
+}
+
+/// Print the available templates, as text or as a JSON array
+pub async fn list_templates<C: ProductConfig>(
+    config: &C,
+    template_dir: &Option<PathBuf>,
+    json: bool,
+) -> Result<()> {
+    let mut fetcher = match template_dir {
+        Some(path) => TemplateFetcher::from_local(path.clone(), config.user_agent()),
+        None => TemplateFetcher::from_config(config)?,
+    };
+    let catalog = fetch_catalog(&mut fetcher).await?;
+
+    for (id, error) in &catalog.unavailable {
+        eprintln!(
+            "{} Could not load template '{}': {}",
+            "Warning:".yellow(),
+            id,
+            error
+        );
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&catalog.templates)?);
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("{} templates ({})", config.display_name(), fetcher.source())
+            .cyan()
+            .bold()
+    );
+    println!();
+
+    for template in &catalog.templates {
+        println!(
+            "  {}  {}  {}",
+            template.id.bold(),
+            template.version.dimmed(),
+            template.name
+        );
+        println!("      {}", template.description);
+
+        let mut details = Vec::new();
+        let languages: Vec<String> = template
+            .requires
+            .iter()
+            .map(|l| format!("{} (required)", l))
+            .chain(
+                template
+                    .optional
+                    .iter()
+                    .map(|l| format!("{} (optional)", l)),
+            )
+            .collect();
+        if !languages.is_empty() {
+            details.push(format!("languages: {}", languages.join(", ")));
+        }
+        if let Some(min) = &template.min_iii_version {
+            details.push(format!("iii >= {}", min));
+        }
+        if !template.tags.is_empty() {
+            details.push(format!("tags: {}", template.tags.join(", ")));
+        }
+        if !details.is_empty() {
+            println!("      {}", details.join("  |  ").dimmed());
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &std::path::Path, path: &str, content: &str) {
+        let full = dir.join(path);
+        std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+        std::fs::write(full, content).unwrap();
+    }
+
+    #[tokio::test]
+    async fn fetch_catalog_summarises_each_template() {
+        let tmp = tempfile::tempdir().unwrap();
+        write(
+            tmp.path(),
+            "template.yaml",
+            "templates:\n  - api\n  - broken\n",
+        );
+        write(
+            tmp.path(),
+            "api/template.yaml",
+            "name: API\ndescription: API worker\nversion: '0.2.0'\nmin_iii_version: '0.11.0'\ntags: [http]\nrequires: [typescript]\noptional: [python]\nfiles:\n  - README.md\n",
+        );
+        write(tmp.path(), "api/README.md", "# API\n");
+
+        let mut fetcher = TemplateFetcher::from_local(tmp.path().to_path_buf(), "test");
+        let catalog = fetch_catalog(&mut fetcher).await.unwrap();
+
+        assert_eq!(
+            catalog.templates,
+            vec![TemplateSummary {
+                id: "api".to_string(),
+                name: "API".to_string(),
+                description: "API worker".to_string(),
+                version: "0.2.0".to_string(),
+                min_iii_version: Some("0.11.0".to_string()),
+                requires: vec!["typescript".to_string()],
+                optional: vec!["python".to_string()],
+                tags: vec!["http".to_string()],
+            }]
+        );
+        assert_eq!(catalog.unavailable.len(), 1);
+        assert_eq!(catalog.unavailable[0].0, "broken");
+
+        let json = serde_json::to_value(&catalog.templates).unwrap();
+        assert_eq!(json[0]["id"], "api");
+        assert_eq!(json[0]["min_iii_version"], "0.11.0");
+    }
+}
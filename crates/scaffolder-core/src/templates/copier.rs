@@ -2,20 +2,147 @@
 
 use crate::runtime::check::Language;
 use crate::templates::fetcher::TemplateFetcher;
-use crate::templates::manifest::{FileLanguage, LanguageFiles, TemplateManifest};
+use crate::templates::known_files;
+use crate::templates::manifest::{
+    self, FileLanguage, LanguageFiles, OptionValues, TemplateFile, TemplateManifest, VariableValues,
+};
 use anyhow::{Context, Result};
 use std::path::Path;
 use tokio::fs;
 
-/// Copy template files to the target directory, filtering by selected languages
+/// A template file rendered for the selected languages and options, ready to be written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedFile {
+    /// Destination path relative to the project root
+    pub dest: String,
+
+    /// File contents after option blocks have been applied
+    pub content: Vec<u8>,
+}
+
+/// Copy template files to the target directory, filtering by selected languages and options
 pub async fn copy_template(
     fetcher: &mut TemplateFetcher,
     template_name: &str,
     manifest: &TemplateManifest,
     target_dir: &Path,
     selected_languages: &[Language],
+    options: &OptionValues,
     language_files: &LanguageFiles,
 ) -> Result<Vec<String>> {
+    let files = render_template(
+        fetcher,
+        template_name,
+        manifest,
+        selected_languages,
+        options,
+        language_files,
+    )
+    .await?;
+
+    write_files(target_dir, &files).await
+}
+
+/// Fetch and render every template file selected by languages and options, without writing
+pub async fn render_template(
+    fetcher: &mut TemplateFetcher,
+    template_name: &str,
+    manifest: &TemplateManifest,
+    selected_languages: &[Language],
+    options: &OptionValues,
+    language_files: &LanguageFiles,
+) -> Result<Vec<RenderedFile>> {
+    let mut rendered = Vec::new();
+
+    for selection in select_files(manifest, selected_languages, options, language_files) {
+        if !selection.is_included() {
+            continue;
+        }
+
+        let mut content = fetcher
+            .fetch_file_bytes(template_name, &selection.source)
+            .await?;
+        if !manifest.options.is_empty() {
+            content = apply_option_blocks(content, options)
+                .with_context(|| format!("Invalid option block in {}", selection.source))?;
+        }
+
+        rendered.push(RenderedFile {
+            dest: selection.dest,
+            content,
+        });
+    }
+
+    Ok(rendered)
+}
+
+/// Why a manifest entry is or isn't part of the generated project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDecision {
+    /// Copied into the project
+    Included,
+    /// The file's language was not selected
+    LanguageNotSelected,
+    /// No `language_files` pattern matches the file and it has no language override
+    NoLanguageMatch,
+    /// Gated behind a disabled option (holds the option name)
+    OptionDisabled(String),
+}
+
+/// A manifest entry together with the decision made for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSelection {
+    /// Source path relative to the template directory
+    pub source: String,
+    /// Destination path relative to the project root
+    pub dest: String,
+    /// Language the file is associated with, if any
+    pub language: Option<FileLanguage>,
+    pub decision: FileDecision,
+}
+
+impl FileSelection {
+    pub fn is_included(&self) -> bool {
+        self.decision == FileDecision::Included
+    }
+}
+
+/// Decide for every manifest entry whether it is copied, and why not when it isn't
+pub fn select_files(
+    manifest: &TemplateManifest,
+    selected_languages: &[Language],
+    options: &OptionValues,
+    language_files: &LanguageFiles,
+) -> Vec<FileSelection> {
+    manifest
+        .files
+        .iter()
+        .map(|file| {
+            let dest = file.destination();
+            let language = file.language(language_files);
+
+            let decision = if language.is_none() {
+                FileDecision::NoLanguageMatch
+            } else if !should_include_entry(file, selected_languages, language_files) {
+                FileDecision::LanguageNotSelected
+            } else if let Some(option) = manifest.disabled_option_for(dest, options) {
+                FileDecision::OptionDisabled(option.name.clone())
+            } else {
+                FileDecision::Included
+            };
+
+            FileSelection {
+                source: file.path.clone(),
+                dest: dest.to_string(),
+                language,
+                decision,
+            }
+        })
+        .collect()
+}
+
+/// Write rendered files below the target directory, returning the written paths
+pub async fn write_files(target_dir: &Path, files: &[RenderedFile]) -> Result<Vec<String>> {
     // Ensure target directory exists
     fs::create_dir_all(target_dir)
         .await
@@ -23,44 +150,272 @@ pub async fn copy_template(
 
     let mut copied_files = Vec::new();
 
-    for file_path in &manifest.files {
-        // Check if this file should be included based on language selection
-        if should_include_file(file_path, selected_languages, language_files) {
-            // Ensure parent directories exist
-            let target_path = target_dir.join(file_path);
-            if let Some(parent) = target_path.parent() {
-                fs::create_dir_all(parent)
-                    .await
-                    .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
-            }
-
-            // Fetch and write the file
-            let content = fetcher.fetch_file_bytes(template_name, file_path).await?;
-            fs::write(&target_path, &content)
+    for file in files {
+        if !manifest::is_project_relative(&file.dest) {
+            anyhow::bail!("Refusing to write outside the project: {}", file.dest);
+        }
+        // Ensure parent directories exist
+        let target_path = target_dir.join(&file.dest);
+        if let Some(parent) = target_path.parent() {
+            fs::create_dir_all(parent)
                 .await
-                .with_context(|| format!("Failed to write file: {}", target_path.display()))?;
-
-            copied_files.push(file_path.clone());
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
         }
+
+        fs::write(&target_path, &file.content)
+            .await
+            .with_context(|| format!("Failed to write file: {}", target_path.display()))?;
+
+        copied_files.push(file.dest.clone());
     }
 
     Ok(copied_files)
 }
 
+/// A file a scaffold would write, as reported by dry runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedWrite {
+    /// Destination path relative to the project root
+    pub dest: String,
+
+    /// Size of the rendered content in bytes
+    pub size: usize,
+
+    /// Whether a file already exists at the destination
+    pub overwrites: bool,
+}
+
+/// Work out what writing rendered files would do, without touching disk
+pub fn plan_writes(target_dir: &Path, files: &[RenderedFile]) -> Vec<PlannedWrite> {
+    files
+        .iter()
+        .map(|file| PlannedWrite {
+            dest: file.dest.clone(),
+            size: file.content.len(),
+            overwrites: target_dir.join(&file.dest).exists(),
+        })
+        .collect()
+}
+
+/// Render planned writes as an indented file tree, one line per directory or file
+pub fn file_tree_lines(planned: &[PlannedWrite]) -> Vec<String> {
+    let mut sorted: Vec<&PlannedWrite> = planned.iter().collect();
+    sorted.sort_by(|a, b| a.dest.cmp(&b.dest));
+
+    let mut lines = Vec::new();
+    let mut open_dirs: Vec<&str> = Vec::new();
+
+    for file in sorted {
+        let mut parts: Vec<&str> = file.dest.split('/').collect();
+        let name = parts.pop().unwrap_or_default();
+
+        let shared = open_dirs
+            .iter()
+            .zip(&parts)
+            .take_while(|(open, part)| open == part)
+            .count();
+        open_dirs.truncate(shared);
+        for dir in &parts[shared..] {
+            lines.push(format!("{}{}/", "  ".repeat(open_dirs.len()), dir));
+            open_dirs.push(dir);
+        }
+
+        lines.push(format!(
+            "{}{} ({}){}",
+            "  ".repeat(open_dirs.len()),
+            name,
+            format_size(file.size),
+            if file.overwrites { " [overwrite]" } else { "" }
+        ));
+    }
+
+    lines
+}
+
+/// Human-readable file size
+fn format_size(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// What to do when a file being written already exists with different content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Replace the existing file, including well-known files that are otherwise merged
+    Overwrite,
+    /// Keep the existing file
+    Skip,
+    /// Rename the existing file to `<name>.bak` and write the new one
+    Backup,
+    /// Ask for each file, showing a diff
+    Prompt,
+    /// Abort before writing anything
+    Fail,
+}
+
+/// Decision for a single conflicting file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    Overwrite,
+    Skip,
+    Backup,
+}
+
+/// Rendered files whose destination already exists with different content
+///
+/// Well-known files that can be merged with the existing content are not conflicts.
+pub fn find_conflicts(target_dir: &Path, files: &[RenderedFile]) -> Vec<String> {
+    files
+        .iter()
+        .filter(|file| {
+            std::fs::read(target_dir.join(&file.dest)).is_ok_and(|existing| {
+                existing != file.content
+                    && known_files::merge_known_file(&file.dest, &existing, &file.content).is_none()
+            })
+        })
+        .map(|file| file.dest.clone())
+        .collect()
+}
+
+/// Outcome of resolving conflicts, before anything is written
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolvedWrites {
+    /// Files to write with their final content: merged well-known files carry the merged
+    /// content and skipped files are left out
+    pub files: Vec<RenderedFile>,
+    /// The template's own content for each file in `files`, which the base snapshot and the
+    /// lock record so that lines merged in from existing files count as local changes
+    pub template_files: Vec<RenderedFile>,
+    /// Existing files to rename before writing, as `(dest, backup path)`
+    pub backups: Vec<(String, String)>,
+    /// Existing files left as they were
+    pub skipped: Vec<String>,
+    /// Well-known files merged with the existing content, with what was added
+    pub merged: Vec<(String, String)>,
+}
+
+/// Decide how each rendered file gets written, asking `resolve` about conflicting files
+///
+/// `resolve` receives the destination path, the existing content and the new content.
+/// Files that already hold exactly the new content are not treated as conflicts. With
+/// `merge_known` set, well-known files (`.gitignore`, `package.json`, ...) are merged
+/// instead; without it they go through `resolve` like any other file.
+pub async fn resolve_writes<F>(
+    target_dir: &Path,
+    files: &[RenderedFile],
+    merge_known: bool,
+    mut resolve: F,
+) -> Result<ResolvedWrites>
+where
+    F: FnMut(&str, &[u8], &[u8]) -> Result<ConflictResolution>,
+{
+    let mut resolved = ResolvedWrites::default();
+
+    for file in files {
+        let target_path = target_dir.join(&file.dest);
+        let resolution = match fs::read(&target_path).await {
+            Ok(existing) if existing != file.content => {
+                let merged = merge_known
+                    .then(|| known_files::merge_known_file(&file.dest, &existing, &file.content))
+                    .flatten();
+                if let Some(merged) = merged {
+                    resolved.files.push(RenderedFile {
+                        dest: file.dest.clone(),
+                        content: merged.content,
+                    });
+                    resolved.merged.push((file.dest.clone(), merged.summary));
+                    resolved.template_files.push(file.clone());
+                    continue;
+                }
+                resolve(&file.dest, &existing, &file.content)?
+            }
+            _ => ConflictResolution::Overwrite,
+        };
+
+        match resolution {
+            ConflictResolution::Overwrite => {
+                resolved.files.push(file.clone());
+                resolved.template_files.push(file.clone());
+            }
+            ConflictResolution::Skip => resolved.skipped.push(file.dest.clone()),
+            ConflictResolution::Backup => {
+                let backup = backup_path(target_dir, &file.dest);
+                resolved.backups.push((file.dest.clone(), backup));
+                resolved.files.push(file.clone());
+                resolved.template_files.push(file.clone());
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// First free `<dest>.bak`, `<dest>.bak.1`, ... path for a backup
+fn backup_path(target_dir: &Path, dest: &str) -> String {
+    let mut candidate = format!("{}.bak", dest);
+    let mut index = 1;
+    while target_dir.join(&candidate).exists() {
+        candidate = format!("{}.bak.{}", dest, index);
+        index += 1;
+    }
+    candidate
+}
+
+/// Unified diff between existing and new content, or `None` if either side is binary
+pub fn unified_diff(path: &str, existing: &[u8], new: &[u8]) -> Option<String> {
+    let existing = std::str::from_utf8(existing).ok()?;
+    let new = std::str::from_utf8(new).ok()?;
+    Some(
+        similar::TextDiff::from_lines(existing, new)
+            .unified_diff()
+            .context_radius(3)
+            .header(
+                &format!("{} (existing)", path),
+                &format!("{} (template)", path),
+            )
+            .to_string(),
+    )
+}
+
+/// Determine if a manifest entry should be included, honoring its language override
+fn should_include_entry(
+    file: &TemplateFile,
+    selected_languages: &[Language],
+    language_files: &LanguageFiles,
+) -> bool {
+    match file.language {
+        Some(language) => is_language_selected(Some(language), selected_languages),
+        None => should_include_file(file.destination(), selected_languages, language_files),
+    }
+}
+
 /// Determine if a file should be included based on selected languages and language_files config
 fn should_include_file(
     file_path: &str,
     selected_languages: &[Language],
     language_files: &LanguageFiles,
 ) -> bool {
+    is_language_selected(
+        language_files.get_language_for_file(file_path),
+        selected_languages,
+    )
+}
+
+/// Check whether a file's language is covered by the selected languages
+fn is_language_selected(language: Option<FileLanguage>, selected_languages: &[Language]) -> bool {
     let has_typescript = selected_languages.contains(&Language::TypeScript);
     let has_javascript = selected_languages.contains(&Language::JavaScript);
     let has_python = selected_languages.contains(&Language::Python);
     let has_rust = selected_languages.contains(&Language::Rust);
     let has_js_or_ts = has_typescript || has_javascript;
 
-    // Check if file matches any pattern from config
-    match language_files.get_language_for_file(file_path) {
+    match language {
         Some(FileLanguage::Common) => true,
         Some(FileLanguage::Python) => has_python,
         Some(FileLanguage::TypeScript) => has_typescript,
@@ -71,6 +426,158 @@ fn should_include_file(
     }
 }
 
+/// Strip `@if <option>` / `@endif` sections from text files based on option values
+///
+/// Marker lines may be wrapped in a comment (`# @if docker`, `// @if !docker`) and are
+/// removed from the output. Only options in `options` (the template's declared options)
+/// start a block; files without such a marker, binary and non-UTF-8 files are returned
+/// unchanged. An `@if` without its `@endif`, or the reverse, is an error.
+fn apply_option_blocks(content: Vec<u8>, options: &OptionValues) -> Result<Vec<u8>> {
+    let Ok(text) = std::str::from_utf8(&content) else {
+        return Ok(content);
+    };
+    let has_blocks = text.contains("@if ")
+        && text.split_inclusive('\n').any(|line| {
+            matches!(
+                parse_block_marker(line, options),
+                Some(BlockMarker::If { .. })
+            )
+        });
+    if !has_blocks {
+        return Ok(content);
+    }
+
+    let mut output = String::with_capacity(text.len());
+    // Each entry holds the line the block opened on and whether it is being kept
+    let mut stack: Vec<(usize, bool)> = Vec::new();
+
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        match parse_block_marker(line, options) {
+            Some(BlockMarker::If { name, negated }) => {
+                let enabled = options[name] != negated;
+                let parent_kept = stack.last().is_none_or(|(_, kept)| *kept);
+                stack.push((index + 1, parent_kept && enabled));
+            }
+            Some(BlockMarker::EndIf) => {
+                if stack.pop().is_none() {
+                    anyhow::bail!("`@endif` on line {} has no matching `@if`", index + 1);
+                }
+            }
+            None => {
+                if stack.last().is_none_or(|(_, kept)| *kept) {
+                    output.push_str(line);
+                }
+            }
+        }
+    }
+
+    if let Some((line, _)) = stack.last() {
+        anyhow::bail!("`@if` on line {} is never closed with `@endif`", line);
+    }
+    Ok(output.into_bytes())
+}
+
+/// Substitute variable values for `{{name}}` (or `{{ name }}`) placeholders in text files
+///
+/// Each file is scanned once, so values are inserted as-is even if they contain `{{...}}`
+/// themselves. Only `{{` followed by a bare name and `}}` is a placeholder: other braces
+/// (JSX `style={{`, `{{ a.b }}`, GitHub Actions `${{ ... }}`) are left alone. Templates
+/// that declare no variables are not scanned at all; otherwise a placeholder without a
+/// value is an error. Binary and non-UTF-8 files are unchanged.
+pub fn apply_variables(files: &mut [RenderedFile], variables: &VariableValues) -> Result<()> {
+    if variables.is_empty() {
+        return Ok(());
+    }
+    for file in files {
+        let Ok(text) = std::str::from_utf8(&file.content) else {
+            continue;
+        };
+        if !text.contains("{{") {
+            continue;
+        }
+        let text = substitute_variables(text, variables).map_err(|name| {
+            anyhow::anyhow!(
+                "{} uses variable '{}', which the template does not declare",
+                file.dest,
+                name
+            )
+        })?;
+        file.content = text.into_bytes();
+    }
+    Ok(())
+}
+
+/// Replace every placeholder in one pass, or return the first name without a value
+fn substitute_variables(text: &str, variables: &VariableValues) -> Result<String, String> {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match placeholder_name(after).filter(|_| !output.ends_with('$')) {
+            Some((name, len)) => {
+                let value = variables.get(name).ok_or_else(|| name.to_string())?;
+                output.push_str(value);
+                rest = &after[len..];
+            }
+            None => {
+                output.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Read `name}}` or ` name }}` at the start of `text`: the name and the length consumed
+fn placeholder_name(text: &str) -> Option<(&str, usize)> {
+    let inner_len = text.find("}}")?;
+    let inner = &text[..inner_len];
+    let name = inner.strip_prefix(' ').unwrap_or(inner);
+    let name = name.strip_suffix(' ').unwrap_or(name);
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    valid.then_some((name, inner_len + 2))
+}
+
+/// An option block marker line
+enum BlockMarker<'a> {
+    If { name: &'a str, negated: bool },
+    EndIf,
+}
+
+/// Recognize a marker line, ignoring common comment leaders; `@if` only counts for
+/// options in `options`
+fn parse_block_marker<'a>(line: &'a str, options: &OptionValues) -> Option<BlockMarker<'a>> {
+    let mut trimmed = line.trim();
+    for leader in ["<!--", "#", "//"] {
+        if let Some(rest) = trimmed.strip_prefix(leader) {
+            trimmed = rest.trim();
+            break;
+        }
+    }
+    let trimmed = trimmed.strip_suffix("-->").unwrap_or(trimmed).trim();
+
+    if trimmed == "@endif" {
+        return Some(BlockMarker::EndIf);
+    }
+
+    let name = trimmed.strip_prefix("@if ")?.trim();
+    let (name, negated) = match name.strip_prefix('!') {
+        Some(name) => (name.trim(), true),
+        None => (name, false),
+    };
+    options
+        .contains_key(name)
+        .then_some(BlockMarker::If { name, negated })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,6 +606,198 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_file_tree_groups_directories() {
+        let planned = vec![
+            PlannedWrite {
+                dest: "workers/api/main.py".to_string(),
+                size: 2048,
+                overwrites: false,
+            },
+            PlannedWrite {
+                dest: "README.md".to_string(),
+                size: 12,
+                overwrites: true,
+            },
+            PlannedWrite {
+                dest: "workers/api/pyproject.toml".to_string(),
+                size: 100,
+                overwrites: false,
+            },
+            PlannedWrite {
+                dest: "workers/web/index.ts".to_string(),
+                size: 10,
+                overwrites: false,
+            },
+        ];
+
+        assert_eq!(
+            file_tree_lines(&planned),
+            vec![
+                "README.md (12 B) [overwrite]",
+                "workers/",
+                "  api/",
+                "    main.py (2.0 KB)",
+                "    pyproject.toml (100 B)",
+                "  web/",
+                "    index.ts (10 B)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_writes_detects_existing_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("README.md"), "old").unwrap();
+        let files = vec![
+            RenderedFile {
+                dest: "README.md".to_string(),
+                content: b"new readme".to_vec(),
+            },
+            RenderedFile {
+                dest: "src/main.ts".to_string(),
+                content: b"x".to_vec(),
+            },
+        ];
+
+        let planned = plan_writes(tmp.path(), &files);
+        assert!(planned[0].overwrites);
+        assert_eq!(planned[0].size, 10);
+        assert!(!planned[1].overwrites);
+    }
+
+    fn rendered(dest: &str, content: &str) -> RenderedFile {
+        RenderedFile {
+            dest: dest.to_string(),
+            content: content.as_bytes().to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_writes_asks_only_about_real_conflicts() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("same.txt"), "same").unwrap();
+        std::fs::write(tmp.path().join("keep.txt"), "mine").unwrap();
+        std::fs::write(tmp.path().join("backup.txt"), "mine").unwrap();
+        std::fs::write(tmp.path().join("backup.txt.bak"), "older").unwrap();
+
+        let files = vec![
+            rendered("same.txt", "same"),
+            rendered("keep.txt", "template"),
+            rendered("backup.txt", "template"),
+            rendered("new.txt", "template"),
+        ];
+        assert_eq!(
+            find_conflicts(tmp.path(), &files),
+            vec!["keep.txt", "backup.txt"]
+        );
+
+        let mut asked = Vec::new();
+        let resolved = resolve_writes(tmp.path(), &files, true, |path, _, _| {
+            asked.push(path.to_string());
+            Ok(if path == "keep.txt" {
+                ConflictResolution::Skip
+            } else {
+                ConflictResolution::Backup
+            })
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(asked, vec!["keep.txt", "backup.txt"]);
+        assert_eq!(resolved.skipped, vec!["keep.txt"]);
+        assert_eq!(
+            resolved.backups,
+            vec![("backup.txt".to_string(), "backup.txt.bak.1".to_string())]
+        );
+        let dests: Vec<&str> = resolved.files.iter().map(|f| f.dest.as_str()).collect();
+        assert_eq!(dests, vec!["same.txt", "backup.txt", "new.txt"]);
+    }
+
+    #[test]
+    fn test_apply_variables_substitutes_placeholders_once() {
+        let mut files = vec![
+            rendered("README.md", "# {{service}}\nPort {{ port }}\n"),
+            rendered(
+                "src/app.tsx",
+                "<div style={{\n  color }}>{{ a.b }}</div>\nrun: ${{ port }}\n",
+            ),
+            RenderedFile {
+                dest: "logo.png".to_string(),
+                content: vec![0xff, b'{', b'{'],
+            },
+        ];
+        // A value that looks like a placeholder is inserted as-is
+        let variables = VariableValues::from([
+            ("service".to_string(), "{{port}}".to_string()),
+            ("port".to_string(), "3000".to_string()),
+        ]);
+
+        apply_variables(&mut files, &variables).unwrap();
+
+        assert_eq!(files[0].content, b"# {{port}}\nPort 3000\n");
+        assert_eq!(
+            files[1].content,
+            b"<div style={{\n  color }}>{{ a.b }}</div>\nrun: ${{ port }}\n"
+        );
+        assert_eq!(files[2].content, vec![0xff, b'{', b'{']);
+
+        let mut undeclared = vec![rendered("README.md", "{{service}} {{region}}\n")];
+        let err = apply_variables(&mut undeclared, &variables).unwrap_err();
+        assert!(err.to_string().contains("'region'"), "{}", err);
+
+        // Templates without variables are copied verbatim
+        let mut plain = vec![rendered("App.vue", "<p>{{ message }}</p>\n")];
+        apply_variables(&mut plain, &VariableValues::new()).unwrap();
+        assert_eq!(plain[0].content, b"<p>{{ message }}</p>\n");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_writes_merges_only_root_known_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join(".gitignore"), "target\n").unwrap();
+        std::fs::create_dir_all(tmp.path().join("workers/api")).unwrap();
+        std::fs::write(tmp.path().join("workers/api/.gitignore"), "dist\n").unwrap();
+
+        let files = vec![
+            rendered(".gitignore", "node_modules\n"),
+            rendered("workers/api/.gitignore", "node_modules\n"),
+        ];
+        assert_eq!(
+            find_conflicts(tmp.path(), &files),
+            vec!["workers/api/.gitignore"]
+        );
+
+        let mut asked = Vec::new();
+        let resolved = resolve_writes(tmp.path(), &files, true, |path, _, _| {
+            asked.push(path.to_string());
+            Ok(ConflictResolution::Skip)
+        })
+        .await
+        .unwrap();
+        assert_eq!(asked, vec!["workers/api/.gitignore"]);
+        assert_eq!(resolved.files.len(), 1);
+        assert_eq!(resolved.files[0].content, b"target\n\nnode_modules\n");
+        assert_eq!(resolved.skipped, vec!["workers/api/.gitignore"]);
+
+        // Without merging, the root file is an ordinary conflict
+        let resolved = resolve_writes(tmp.path(), &files, false, |_, _, _| {
+            Ok(ConflictResolution::Overwrite)
+        })
+        .await
+        .unwrap();
+        assert!(resolved.merged.is_empty());
+        assert_eq!(resolved.files, files);
+    }
+
+    #[test]
+    fn test_unified_diff_shows_changes() {
+        let diff = unified_diff("README.md", b"a\nb\n", b"a\nc\n").unwrap();
+        assert!(diff.contains("--- README.md (existing)"));
+        assert!(diff.contains("-b\n+c\n"));
+        assert!(unified_diff("logo.png", &[0xff, 0xfe], b"x").is_none());
+    }
+
     #[test]
     fn test_should_include_typescript_files() {
         let languages = vec![Language::TypeScript];
@@ -175,4 +874,117 @@ mod tests {
         assert!(!should_include_file("requirements.txt", &ts_only, &lf));
         assert!(should_include_file("requirements.txt", &py_only, &lf));
     }
+
+    #[test]
+    fn test_entry_language_override() {
+        let lf = test_language_files();
+        let pinned = TemplateFile {
+            language: Some(FileLanguage::Python),
+            ..TemplateFile::new("scripts/setup.sh")
+        };
+        let renamed = TemplateFile {
+            dest: Some("package.json".to_string()),
+            ..TemplateFile::new("templates/package.base.json")
+        };
+
+        assert!(should_include_entry(&pinned, &[Language::Python], &lf));
+        assert!(!should_include_entry(&pinned, &[Language::TypeScript], &lf));
+        // Without an override, the destination name is classified
+        assert!(should_include_entry(&renamed, &[Language::TypeScript], &lf));
+        assert!(!should_include_entry(&renamed, &[Language::Python], &lf));
+    }
+
+    #[test]
+    fn test_select_files_explains_exclusions() {
+        let manifest: TemplateManifest = serde_yaml::from_str(
+            "name: T\ndescription: d\nversion: '1.0.0'\n\
+             options:\n  - name: docker\n    files: [Dockerfile]\n\
+             files:\n  - package.json\n  - requirements.txt\n  - Dockerfile\n  - notes.md\n",
+        )
+        .unwrap();
+        let mut lf = test_language_files();
+        lf.common.push("Dockerfile".to_string());
+        let options = manifest.resolve_options(&OptionValues::new()).unwrap();
+
+        let selections = select_files(&manifest, &[Language::TypeScript], &options, &lf);
+        let decisions: Vec<(&str, &FileDecision)> = selections
+            .iter()
+            .map(|s| (s.dest.as_str(), &s.decision))
+            .collect();
+
+        assert_eq!(
+            decisions,
+            vec![
+                ("package.json", &FileDecision::Included),
+                ("requirements.txt", &FileDecision::LanguageNotSelected),
+                (
+                    "Dockerfile",
+                    &FileDecision::OptionDisabled("docker".to_string())
+                ),
+                ("notes.md", &FileDecision::NoLanguageMatch),
+            ]
+        );
+        assert_eq!(selections[1].language, Some(FileLanguage::Python));
+    }
+
+    /// Render with `docker`, `observability` and `redis` declared and only `enabled` on
+    fn try_render(content: &str, enabled: &[&str]) -> Result<String> {
+        let options: OptionValues = ["docker", "observability", "redis"]
+            .iter()
+            .map(|n| (n.to_string(), enabled.contains(n)))
+            .collect();
+        Ok(String::from_utf8(apply_option_blocks(content.as_bytes().to_vec(), &options)?).unwrap())
+    }
+
+    fn render(content: &str, enabled: &[&str]) -> String {
+        try_render(content, enabled).unwrap()
+    }
+
+    #[test]
+    fn test_option_blocks_kept_when_enabled() {
+        let content = "modules:\n  # @if observability\n  - otel\n  # @endif\n  - api\n";
+        assert_eq!(
+            render(content, &["observability"]),
+            "modules:\n  - otel\n  - api\n"
+        );
+        assert_eq!(render(content, &[]), "modules:\n  - api\n");
+    }
+
+    #[test]
+    fn test_option_blocks_negated_and_nested() {
+        let content = "// @if !redis\nmemory\n// @endif\n// @if redis\nredis\n// @if docker\nredis-container\n// @endif\n// @endif\n";
+        assert_eq!(render(content, &[]), "memory\n");
+        assert_eq!(render(content, &["redis"]), "redis\n");
+        assert_eq!(
+            render(content, &["redis", "docker"]),
+            "redis\nredis-container\n"
+        );
+    }
+
+    #[test]
+    fn test_option_blocks_ignore_files_without_markers() {
+        let content = "plain text\n";
+        assert_eq!(render(content, &[]), content);
+    }
+
+    #[test]
+    fn test_option_blocks_ignore_undeclared_names() {
+        // Not a declared option, so not a block: nothing after it is dropped
+        let content = "# @if kafka\nbroker\n# @endif\n";
+        assert_eq!(render(content, &[]), content);
+        let content = "// @if docker\ncontainer\n// @endif\n// @if kafka\nbroker\n";
+        assert_eq!(
+            render(content, &["docker"]),
+            "container\n// @if kafka\nbroker\n"
+        );
+    }
+
+    #[test]
+    fn test_option_blocks_must_be_balanced() {
+        let unclosed = try_render("a\n# @if docker\nb\n", &[]).unwrap_err();
+        assert!(unclosed.to_string().contains("line 2"), "{}", unclosed);
+
+        let stray = try_render("# @if docker\nb\n# @endif\n# @endif\n", &[]).unwrap_err();
+        assert!(stray.to_string().contains("line 4"), "{}", stray);
+    }
 }
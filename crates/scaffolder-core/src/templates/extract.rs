@@ -0,0 +1,362 @@
+//! Turning an existing project into a template folder
+
+use super::authoring::{unclassified_files, ManifestDraft};
+use super::fetcher::TemplateFetcher;
+use super::manifest::{FileLanguage, LanguageFiles};
+use crate::product::ProductConfig;
+use crate::project::{self, PROJECT_META_DIR};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// Directories never copied into a template (dependencies, build output, VCS data)
+const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    ".venv",
+    "venv",
+    "__pycache__",
+    "target",
+];
+
+/// Arguments for `template extract`
+#[derive(Debug, Clone, Default)]
+pub struct ExtractArgs {
+    /// Project to turn into a template
+    pub project_dir: PathBuf,
+
+    /// Template folder to write (must not exist or be empty)
+    pub out_dir: PathBuf,
+
+    /// Template display name (defaults to the project directory name)
+    pub name: Option<String>,
+
+    /// Local registry whose root `language_files` classify the project files
+    pub template_dir: Option<PathBuf>,
+}
+
+/// A template derived from a project, before it is written out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedTemplate {
+    pub name: String,
+    /// Project-relative paths of every file to copy, sorted
+    pub files: Vec<String>,
+    /// Languages inferred from the files, in manifest order
+    pub requires: Vec<String>,
+    /// File names no root pattern matches; listed as template-level `common` files
+    pub unmatched: Vec<String>,
+}
+
+/// Collect a project's files, honoring `.gitignore` and skipping dependency directories
+pub fn collect_project_files(project_dir: &Path) -> Result<Vec<String>> {
+    let walker = ignore::WalkBuilder::new(project_dir)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            !(is_dir && (SKIPPED_DIRS.contains(&name.as_ref()) || name == PROJECT_META_DIR))
+        })
+        .build();
+
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry = entry.with_context(|| format!("Failed to walk {}", project_dir.display()))?;
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(project_dir)
+            .context("Walked file outside the project directory")?;
+        files.push(project::relative_path_string(relative));
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Classify project files and infer the languages the template requires
+pub fn extract_template(
+    name: &str,
+    files: Vec<String>,
+    language_files: &LanguageFiles,
+) -> ExtractedTemplate {
+    let languages: Vec<FileLanguage> = files
+        .iter()
+        .filter_map(|file| language_files.get_language_for_file(file))
+        .collect();
+    let unmatched = unclassified_files(&files, language_files);
+
+    let has = |language: FileLanguage| languages.contains(&language);
+    let mut requires = Vec::new();
+    if has(FileLanguage::TypeScript) {
+        requires.push("typescript".to_string());
+    }
+    // Node-only files (a bare package.json) still need a JS-family language to be copied
+    if has(FileLanguage::JavaScript) || (has(FileLanguage::Node) && requires.is_empty()) {
+        requires.push("javascript".to_string());
+    }
+    if has(FileLanguage::Python) {
+        requires.push("python".to_string());
+    }
+    if has(FileLanguage::Rust) {
+        requires.push("rust".to_string());
+    }
+
+    ExtractedTemplate {
+        name: name.to_string(),
+        files,
+        requires,
+        unmatched,
+    }
+}
+
+impl ExtractedTemplate {
+    /// Manifest for the extracted template, ready for the author to refine
+    pub fn manifest(&self) -> ManifestDraft {
+        ManifestDraft {
+            name: self.name.clone(),
+            description: format!("Template extracted from {}", self.name),
+            version: "0.1.0".to_string(),
+            requires: self.requires.clone(),
+            common_files: self.unmatched.clone(),
+            files: self.files.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Copy the project files and write the manifest into `out_dir`
+pub fn write_template(
+    project_dir: &Path,
+    out_dir: &Path,
+    template: &ExtractedTemplate,
+) -> Result<()> {
+    if out_dir.exists()
+        && std::fs::read_dir(out_dir)
+            .with_context(|| format!("Failed to read {}", out_dir.display()))?
+            .next()
+            .is_some()
+    {
+        anyhow::bail!(
+            "Output directory already exists and is not empty: {}",
+            out_dir.display()
+        );
+    }
+
+    for file in &template.files {
+        let dest = out_dir.join(file);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        std::fs::copy(project_dir.join(file), &dest)
+            .with_context(|| format!("Failed to copy {}", file))?;
+    }
+
+    let manifest_path = out_dir.join("template.yaml");
+    std::fs::write(&manifest_path, template.manifest().render())
+        .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+
+    Ok(())
+}
+
+/// Extract a template from a project and report what was written
+pub async fn run_extract<C: ProductConfig>(config: &C, args: ExtractArgs) -> Result<()> {
+    if !args.project_dir.is_dir() {
+        anyhow::bail!(
+            "Project directory not found: {}",
+            args.project_dir.display()
+        );
+    }
+
+    let fetcher = match &args.template_dir {
+        Some(path) => TemplateFetcher::from_local(path.clone(), config.user_agent()),
+        None => TemplateFetcher::from_config(config)?,
+    };
+    let root_manifest = fetcher.fetch_root_manifest().await?;
+
+    let project_dir = args
+        .project_dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", args.project_dir.display()))?;
+    let name = args.name.clone().unwrap_or_else(|| {
+        project_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "template".to_string())
+    });
+
+    let mut files = collect_project_files(&project_dir)?;
+    // Keep the output folder out of the template when it lives inside the project
+    if let Ok(out_dir) = args.out_dir.canonicalize() {
+        if let Ok(relative) = out_dir.strip_prefix(&project_dir) {
+            let prefix = format!("{}/", project::relative_path_string(relative));
+            files.retain(|f| !f.starts_with(&prefix));
+        }
+    }
+    if files.is_empty() {
+        anyhow::bail!("No files to extract from {}", project_dir.display());
+    }
+
+    let template = extract_template(&name, files, &root_manifest.language_files);
+    write_template(&project_dir, &args.out_dir, &template)?;
+
+    println!(
+        "{}",
+        format!(
+            "Extracted template '{}' to {}",
+            name,
+            args.out_dir.display()
+        )
+        .cyan()
+        .bold()
+    );
+    println!();
+    println!("  {:10} {}", "Files:".dimmed(), template.files.len());
+    println!(
+        "  {:10} {}",
+        "Requires:".dimmed(),
+        if template.requires.is_empty() {
+            "(none)".to_string()
+        } else {
+            template.requires.join(", ")
+        }
+    );
+    if !template.unmatched.is_empty() {
+        println!(
+            "  {:10} {}",
+            "Common:".dimmed(),
+            template.unmatched.join(", ")
+        );
+    }
+    println!();
+
+    let registry = args
+        .out_dir
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let folder = args
+        .out_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| name.clone());
+    println!("  Next steps");
+    println!();
+    println!(
+        "  1.  Review {} (description, optional languages, next_steps)",
+        args.out_dir.join("template.yaml").display()
+    );
+    println!(
+        "  2.  Add '{}' to the templates list in {}",
+        folder,
+        registry.join("template.yaml").display()
+    );
+    println!(
+        "  3.  {} validate --template-dir {}",
+        config.binary_name(),
+        registry.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let full = dir.join(path);
+        std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+        std::fs::write(full, content).unwrap();
+    }
+
+    fn language_files() -> LanguageFiles {
+        LanguageFiles {
+            common: vec!["README.md".to_string(), ".gitignore".to_string()],
+            python: vec!["*.py".to_string(), "requirements.txt".to_string()],
+            typescript: vec!["*.ts".to_string()],
+            node: vec!["package.json".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn collect_skips_ignored_and_dependency_dirs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        write(dir, ".gitignore", "dist/\n*.log\n");
+        write(dir, "README.md", "# App\n");
+        write(dir, "src/index.ts", "x");
+        write(dir, "dist/index.js", "x");
+        write(dir, "debug.log", "x");
+        write(dir, "node_modules/pkg/index.js", "x");
+        write(dir, "workers/py/.venv/lib.py", "x");
+        write(dir, "target/debug/app", "x");
+        write(dir, ".iii/project.ini", "[project]\n");
+
+        assert_eq!(
+            collect_project_files(dir).unwrap(),
+            vec![".gitignore", "README.md", "src/index.ts"]
+        );
+    }
+
+    #[test]
+    fn extract_infers_requires_and_unmatched_files() {
+        let files = vec![
+            "Dockerfile".to_string(),
+            "README.md".to_string(),
+            "api/main.py".to_string(),
+            "web/package.json".to_string(),
+            "web/src/index.ts".to_string(),
+        ];
+        let template = extract_template("app", files, &language_files());
+
+        assert_eq!(template.requires, vec!["typescript", "python"]);
+        assert_eq!(template.unmatched, vec!["Dockerfile"]);
+
+        let only_node =
+            extract_template("app", vec!["package.json".to_string()], &language_files());
+        assert_eq!(only_node.requires, vec!["javascript"]);
+    }
+
+    #[test]
+    fn written_template_passes_validation() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project = tmp.path().join("my app");
+        write(
+            &project,
+            "README.md",
+            "# App\n\nSome text so the zip is not tiny.\n",
+        );
+        write(&project, "Dockerfile", "FROM node\n");
+        write(&project, "src/index.ts", "console.log('hi')\n");
+        write(&project, "package.json", "{}\n");
+
+        let registry = tmp.path().join("registry");
+        let root = "templates: [app]\nlanguage_files:\n  common: [README.md]\n  typescript: ['*.ts']\n  node: [package.json]\n";
+        write(&registry, "template.yaml", root);
+
+        let files = collect_project_files(&project).unwrap();
+        let root_manifest: super::super::RootManifest = serde_yaml::from_str(root).unwrap();
+        let template = extract_template("my app", files, &root_manifest.language_files);
+        write_template(&project, &registry.join("app"), &template).unwrap();
+
+        let manifest: super::super::TemplateManifest = serde_yaml::from_str(
+            &std::fs::read_to_string(registry.join("app/template.yaml")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(manifest.name, "my app");
+        assert_eq!(manifest.requires, vec!["typescript"]);
+        assert_eq!(manifest.language_files.common, vec!["Dockerfile"]);
+        assert_eq!(manifest.files.len(), 4);
+
+        let report = super::super::validate_registry(&registry);
+        assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+
+        let err = write_template(&project, &registry.join("app"), &template).unwrap_err();
+        assert!(err.to_string().contains("not empty"));
+    }
+}
@@ -0,0 +1,260 @@
+//! Post-create hooks declared in template manifests
+
+use crate::events::{Event, EventSink};
+use crate::runtime::check::Language;
+use crate::runtime::tool::stream_output;
+use crate::templates::manifest::{self, OptionValues, PostCreateHook, TemplateManifest};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+use tokio::process::{Child, Command};
+use tokio::time::timeout;
+
+/// How long a single hook may run before it is killed (10 minutes); hooks often install
+/// dependencies, so this is far longer than `INSTALL_TIMEOUT`
+const HOOK_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Hooks whose language and option conditions match the current selection
+pub fn applicable_hooks<'a>(
+    manifest: &'a TemplateManifest,
+    selected_languages: &[Language],
+    options: &OptionValues,
+) -> Vec<&'a PostCreateHook> {
+    manifest
+        .post_create
+        .iter()
+        .filter(|hook| hook.applies_to(selected_languages, options))
+        .collect()
+}
+
+/// Resolve a hook's working directory, refusing paths that escape the project
+pub fn hook_dir(project_dir: &Path, hook: &PostCreateHook) -> Result<PathBuf> {
+    let Some(cwd) = hook.cwd.as_deref() else {
+        return Ok(project_dir.to_path_buf());
+    };
+
+    if !manifest::is_project_relative(cwd) {
+        anyhow::bail!("Hook working directory must be inside the project: {}", cwd);
+    }
+
+    Ok(project_dir.join(cwd))
+}
+
+/// Run a single hook through `sh -c`, reporting it and its output as events
+pub async fn run_hook(
+    project_dir: &Path,
+    hook: &PostCreateHook,
+    events: &dyn EventSink,
+) -> Result<()> {
+    events.emit(Event::HookStarted {
+        command: hook.run.clone(),
+    });
+    let result = spawn_hook(project_dir, hook, events).await;
+    events.emit(Event::HookFinished {
+        command: hook.run.clone(),
+        required: hook.required,
+        error: result.as_ref().err().map(|e| format!("{:#}", e)),
+    });
+    result
+}
+
+async fn spawn_hook(
+    project_dir: &Path,
+    hook: &PostCreateHook,
+    events: &dyn EventSink,
+) -> Result<()> {
+    let dir = hook_dir(project_dir, hook)?;
+    if !dir.is_dir() {
+        anyhow::bail!("Hook working directory not found: {}", dir.display());
+    }
+
+    let mut child = hook_command(&hook.run, &dir)
+        .spawn()
+        .with_context(|| format!("Failed to start hook: {}", hook.run))?;
+
+    let status = wait_for_hook(&mut child, &hook.run, HOOK_TIMEOUT, events).await?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "Hook '{}' failed with exit code: {}",
+            hook.run,
+            status.code().unwrap_or(-1)
+        );
+    }
+
+    Ok(())
+}
+
+/// `sh -c <run>` in `dir`; on Unix it leads its own process group so that a timeout can
+/// stop everything the hook started, not just the shell
+fn hook_command(run: &str, dir: &Path) -> Command {
+    let mut command = Command::new("sh");
+    command
+        .arg("-c")
+        .arg(run)
+        .current_dir(dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    command.process_group(0);
+    command
+}
+
+/// Kill a hook along with the processes in its process group
+async fn kill_hook(child: &mut Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // SAFETY: kill(2) takes plain integers; a negative pid signals the whole group
+        unsafe {
+            libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
+        }
+    }
+    let _ = child.kill().await;
+}
+
+/// Stream a hook's output until it exits, killing it once `limit` has passed
+async fn wait_for_hook(
+    child: &mut Child,
+    run: &str,
+    limit: Duration,
+    events: &dyn EventSink,
+) -> Result<ExitStatus> {
+    let finished = timeout(limit, async {
+        stream_output(child, events).await;
+        child.wait().await
+    })
+    .await;
+
+    match finished {
+        Ok(status) => status.with_context(|| format!("Failed to wait for hook: {}", run)),
+        Err(_) => {
+            kill_hook(child).await;
+            anyhow::bail!("Hook '{}' timed out after {} seconds", run, limit.as_secs());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::NoEvents;
+    use std::sync::Mutex;
+
+    fn hook(run: &str, cwd: Option<&str>) -> PostCreateHook {
+        PostCreateHook {
+            run: run.to_string(),
+            description: None,
+            cwd: cwd.map(String::from),
+            languages: Vec::new(),
+            options: Vec::new(),
+            required: false,
+        }
+    }
+
+    #[test]
+    fn hook_dir_defaults_to_project_root() {
+        let dir = hook_dir(Path::new("/tmp/app"), &hook("ls", None)).unwrap();
+        assert_eq!(dir, PathBuf::from("/tmp/app"));
+    }
+
+    #[test]
+    fn hook_dir_rejects_escaping_paths() {
+        assert!(hook_dir(Path::new("/tmp/app"), &hook("ls", Some("../other"))).is_err());
+        assert!(hook_dir(Path::new("/tmp/app"), &hook("ls", Some("/etc"))).is_err());
+        assert!(hook_dir(Path::new("/tmp/app"), &hook("ls", Some("workers/api"))).is_ok());
+    }
+
+    #[tokio::test]
+    async fn run_hook_runs_in_working_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("workers/api")).unwrap();
+
+        run_hook(
+            tmp.path(),
+            &hook("touch created.txt", Some("workers/api")),
+            &NoEvents,
+        )
+        .await
+        .unwrap();
+
+        assert!(tmp.path().join("workers/api/created.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn run_hook_reports_failure() {
+        let tmp = tempfile::tempdir().unwrap();
+        let err = run_hook(tmp.path(), &hook("exit 3", None), &NoEvents)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("exit code: 3"));
+    }
+
+    #[tokio::test]
+    async fn wait_for_hook_kills_hooks_that_run_too_long() {
+        let tmp = tempfile::tempdir().unwrap();
+        // Not `exec`, so `sleep` runs as a child of the shell
+        let mut child = hook_command("sleep 30; true", tmp.path()).spawn().unwrap();
+        let group = child.id().unwrap() as libc::pid_t;
+        let started = std::time::Instant::now();
+
+        let err = wait_for_hook(
+            &mut child,
+            "sleep 30; true",
+            Duration::from_millis(200),
+            &NoEvents,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(err.to_string().contains("timed out"));
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(child.try_wait().unwrap().is_some());
+
+        // The orphaned `sleep` is reaped by init shortly after it is killed
+        let group_gone = || unsafe { libc::kill(-group, 0) } == -1;
+        for _ in 0..50 {
+            if group_gone() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        assert!(group_gone(), "processes left in the hook's group");
+    }
+
+    #[tokio::test]
+    async fn run_hook_reports_output_as_events() {
+        let tmp = tempfile::tempdir().unwrap();
+        let events = Mutex::new(Vec::new());
+        let sink = |event: Event| events.lock().unwrap().push(event);
+
+        run_hook(tmp.path(), &hook("echo hello; echo oops >&2", None), &sink)
+            .await
+            .unwrap();
+
+        let events = events.into_inner().unwrap();
+        assert_eq!(
+            events.first(),
+            Some(&Event::HookStarted {
+                command: "echo hello; echo oops >&2".to_string()
+            })
+        );
+        assert!(events.contains(&Event::ProcessOutput {
+            line: "hello".to_string(),
+            stderr: false
+        }));
+        assert!(events.contains(&Event::ProcessOutput {
+            line: "oops".to_string(),
+            stderr: true
+        }));
+        assert_eq!(
+            events.last(),
+            Some(&Event::HookFinished {
+                command: "echo hello; echo oops >&2".to_string(),
+                required: false,
+                error: None
+            })
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Showing a template's manifest and the files it would generate
+
+use super::copier::{self, FileDecision};
+use super::fetcher::TemplateFetcher;
+use super::manifest::{self, LanguageFiles, OptionValues, TemplateManifest};
+use crate::product::ProductConfig;
+use crate::runtime::check::Language;
+use anyhow::Result;
+use colored::Colorize;
+use std::path::PathBuf;
+
+/// Arguments for the `info` command
+#[derive(Debug, Clone, Default)]
+pub struct InfoArgs {
+    /// Local template registry to read instead of the remote one
+    pub template_dir: Option<PathBuf>,
+
+    /// Template to describe
+    pub template: String,
+
+    /// Languages to plan for (defaults to every language the template offers)
+    pub languages: Option<Vec<String>>,
+
+    /// Option assignments (`name=true`) to plan for, on top of the option defaults
+    pub options: Vec<String>,
+}
+
+/// Languages a project would be created with
+///
+/// Required languages are always part of the result. Optional ones are added when
+/// requested, or all of them when nothing is requested (as with `create --yes`).
+pub fn resolve_languages(
+    manifest: &TemplateManifest,
+    requested: Option<&[String]>,
+) -> Result<Vec<Language>> {
+    let requested = match requested {
+        Some(names) => {
+            let mut languages = Vec::new();
+            for name in names {
+                match Language::parse(name) {
+                    Some(lang) => languages.push(lang),
+                    None => anyhow::bail!("Unknown language: {}", name),
+                }
+            }
+            Some(languages)
+        }
+        None => None,
+    };
+
+    let mut selected = Vec::new();
+    let mut offered = Vec::new();
+    for (name, lang) in [
+        ("typescript", Language::TypeScript),
+        ("javascript", Language::JavaScript),
+        ("python", Language::Python),
+        ("rust", Language::Rust),
+    ] {
+        if manifest.is_required(name) {
+            selected.push(lang);
+            offered.push(lang);
+        } else if manifest.is_optional(name) {
+            offered.push(lang);
+            if requested.as_ref().is_none_or(|r| r.contains(&lang)) {
+                selected.push(lang);
+            }
+        }
+    }
+
+    if let Some(unknown) = requested
+        .iter()
+        .flatten()
+        .find(|lang| !offered.contains(lang))
+    {
+        let available: Vec<&str> = offered.iter().map(|l| l.display_name()).collect();
+        anyhow::bail!(
+            "Template '{}' does not offer {}. Available languages: {}",
+            manifest.name,
+            unknown.display_name(),
+            if available.is_empty() {
+                "(none)".to_string()
+            } else {
+                available.join(", ")
+            }
+        );
+    }
+
+    Ok(selected)
+}
+
+/// Print a template's manifest, merged language files, next steps and file plan
+pub async fn show_template_info<C: ProductConfig>(config: &C, args: InfoArgs) -> Result<()> {
+    let mut fetcher = match &args.template_dir {
+        Some(path) => TemplateFetcher::from_local(path.clone(), config.user_agent()),
+        None => TemplateFetcher::from_config(config)?,
+    };
+
+    let root_manifest = fetcher.fetch_root_manifest().await?;
+    if !root_manifest.templates.contains(&args.template) {
+        anyhow::bail!(
+            "Template '{}' not found. Available templates: {}",
+            args.template,
+            root_manifest.templates.join(", ")
+        );
+    }
+
+    let manifest = fetcher.fetch_template_manifest(&args.template).await?;
+    let language_files = root_manifest.language_files_for(&manifest);
+
+    let languages = resolve_languages(&manifest, args.languages.as_deref())?;
+    let mut answers = OptionValues::new();
+    for assignment in &args.options {
+        let (name, value) = manifest::parse_option_assignment(assignment)?;
+        answers.insert(name, value);
+    }
+    let options = manifest.resolve_options(&answers)?;
+
+    print_manifest(&args.template, &manifest, fetcher.source());
+    print_language_files(&language_files);
+
+    if !manifest.next_steps.is_empty() {
+        println!("{}", "Next steps".bold());
+        for (i, step) in manifest.next_steps.iter().enumerate() {
+            println!("  {}.  {}", i + 1, step);
+        }
+        println!();
+    }
+
+    let selections = copier::select_files(&manifest, &languages, &options, &language_files);
+    let included = selections.iter().filter(|s| s.is_included()).count();
+
+    let language_names: Vec<&str> = languages.iter().map(|l| l.display_name()).collect();
+    let enabled: Vec<&str> = options
+        .iter()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    let mut heading = format!(
+        "Files for {}",
+        if language_names.is_empty() {
+            "no languages".to_string()
+        } else {
+            language_names.join(", ")
+        }
+    );
+    if !manifest.options.is_empty() {
+        heading.push_str(&format!(
+            " with options: {}",
+            if enabled.is_empty() {
+                "(none)".to_string()
+            } else {
+                enabled.join(", ")
+            }
+        ));
+    }
+    println!("{}", heading.bold());
+
+    let width = selections.iter().map(|s| s.dest.len()).max().unwrap_or(0);
+    for selection in &selections {
+        let language = selection.language.map(|l| l.name()).unwrap_or("-");
+        let source = if selection.source != selection.dest {
+            format!(" (from {})", selection.source)
+        } else {
+            String::new()
+        };
+
+        match &selection.decision {
+            FileDecision::Included => println!(
+                "  {} {:width$}  {}{}",
+                "+".green(),
+                selection.dest,
+                language.dimmed(),
+                source.dimmed(),
+            ),
+            decision => {
+                let reason = match decision {
+                    FileDecision::LanguageNotSelected => format!("{} not selected", language),
+                    FileDecision::NoLanguageMatch => {
+                        "no language_files pattern matches".to_string()
+                    }
+                    FileDecision::OptionDisabled(option) => format!("option '{}' is off", option),
+                    FileDecision::Included => unreachable!(),
+                };
+                println!(
+                    "  {} {:width$}  {}{}",
+                    "-".red(),
+                    selection.dest.dimmed(),
+                    reason.yellow(),
+                    source.dimmed(),
+                );
+            }
+        }
+    }
+    println!();
+    println!("{} of {} files selected", included, selections.len());
+
+    Ok(())
+}
+
+fn print_manifest(id: &str, manifest: &TemplateManifest, source: &super::TemplateSource) {
+    println!(
+        "{}  {}",
+        format!("{} ({})", manifest.name, id).cyan().bold(),
+        manifest.version.dimmed()
+    );
+    println!("{}", manifest.description);
+    println!();
+
+    let mut fields: Vec<(&str, String)> = vec![("Source", source.to_string())];
+    if !manifest.requires.is_empty() {
+        let label = if manifest.treat_required_as_included {
+            "Included"
+        } else {
+            "Requires"
+        };
+        fields.push((label, manifest.requires.join(", ")));
+    }
+    if !manifest.optional.is_empty() {
+        fields.push(("Optional", manifest.optional.join(", ")));
+    }
+    if let Some(min) = &manifest.min_iii_version {
+        fields.push(("Min iii", min.clone()));
+    }
+    if !manifest.tags.is_empty() {
+        fields.push(("Tags", manifest.tags.join(", ")));
+    }
+    for (label, value) in fields {
+        println!("  {:10} {}", format!("{}:", label).dimmed(), value);
+    }
+    println!();
+
+    if !manifest.options.is_empty() {
+        println!("{}", "Options".bold());
+        for option in &manifest.options {
+            println!(
+                "  {}  {}  {}",
+                option.name,
+                format!("(default: {})", option.default).dimmed(),
+                option.label()
+            );
+            if !option.files.is_empty() {
+                println!(
+                    "      {}",
+                    format!("gates: {}", option.files.join(", ")).dimmed()
+                );
+            }
+        }
+        println!();
+    }
+
+    if !manifest.post_create.is_empty() {
+        println!("{}", "Post-create hooks".bold());
+        for hook in &manifest.post_create {
+            let mut conditions = Vec::new();
+            if !hook.languages.is_empty() {
+                conditions.push(format!("languages: {}", hook.languages.join(", ")));
+            }
+            if !hook.options.is_empty() {
+                conditions.push(format!("options: {}", hook.options.join(", ")));
+            }
+            if hook.required {
+                conditions.push("required".to_string());
+            }
+            if conditions.is_empty() {
+                println!("  {}", hook.run);
+            } else {
+                println!(
+                    "  {}  {}",
+                    hook.run,
+                    format!("({})", conditions.join("; ")).dimmed()
+                );
+            }
+        }
+        println!();
+    }
+}
+
+fn print_language_files(language_files: &LanguageFiles) {
+    println!("{}", "Language files (root + template)".bold());
+    for (name, patterns) in [
+        ("common", &language_files.common),
+        ("python", &language_files.python),
+        ("typescript", &language_files.typescript),
+        ("javascript", &language_files.javascript),
+        ("node", &language_files.node),
+        ("rust", &language_files.rust),
+    ] {
+        if !patterns.is_empty() {
+            println!(
+                "  {:11} {}",
+                format!("{}:", name).dimmed(),
+                patterns.join(", ")
+            );
+        }
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> TemplateManifest {
+        serde_yaml::from_str(
+            "name: T\ndescription: d\nversion: '1.0.0'\n\
+             requires: [typescript]\noptional: [python, rust]\nfiles: []\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn resolve_languages_defaults_to_every_offered_language() {
+        assert_eq!(
+            resolve_languages(&manifest(), None).unwrap(),
+            vec![Language::TypeScript, Language::Python, Language::Rust]
+        );
+    }
+
+    #[test]
+    fn resolve_languages_keeps_required_and_requested() {
+        let requested = vec!["py".to_string()];
+        assert_eq!(
+            resolve_languages(&manifest(), Some(&requested)).unwrap(),
+            vec![Language::TypeScript, Language::Python]
+        );
+    }
+
+    #[test]
+    fn resolve_languages_rejects_unknown_and_unoffered() {
+        let err = resolve_languages(&manifest(), Some(&["cobol".to_string()])).unwrap_err();
+        assert!(err.to_string().contains("Unknown language: cobol"));
+
+        let err = resolve_languages(&manifest(), Some(&["js".to_string()])).unwrap_err();
+        assert!(err.to_string().contains("does not offer JavaScript"));
+    }
+}
@@ -0,0 +1,393 @@
+//! Merging template output into well-known files that already exist in the target
+//!
+//! Replacing a user's `.gitignore` or `package.json` wholesale loses their changes, so
+//! these files get an additive merge instead: the existing file wins and only missing
+//! entries from the template are added.
+
+use serde_json::Value as JsonValue;
+use serde_yaml::Value as YamlValue;
+
+/// Result of merging template content into an existing file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergedFile {
+    /// Merged content (identical to the existing content when nothing was missing)
+    pub content: Vec<u8>,
+    /// Short description of what was added
+    pub summary: String,
+}
+
+/// `package.json` sections whose missing keys are copied from the template
+const PACKAGE_JSON_SECTIONS: &[&str] = &[
+    "scripts",
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+/// Whether a destination path is one of the files merged instead of replaced
+///
+/// Only files at the project root count: a worker's own `package.json` or `config.yaml`
+/// is regular template content.
+pub fn is_known_file(dest: &str) -> bool {
+    matches!(
+        dest,
+        ".gitignore" | "package.json" | "requirements.txt" | "config.yaml"
+    )
+}
+
+/// Merge template content into an existing well-known file
+///
+/// Returns `None` for other files, and for known files that cannot be parsed, so the
+/// caller falls back to its normal conflict handling.
+pub fn merge_known_file(dest: &str, existing: &[u8], new: &[u8]) -> Option<MergedFile> {
+    if !is_known_file(dest) {
+        return None;
+    }
+    let existing = std::str::from_utf8(existing).ok()?;
+    let new = std::str::from_utf8(new).ok()?;
+
+    let (content, added) = match dest {
+        ".gitignore" => merge_gitignore(existing, new),
+        "package.json" => merge_package_json(existing, new)?,
+        "requirements.txt" => merge_requirements(existing, new),
+        "config.yaml" => merge_engine_config(existing, new)?,
+        _ => return None,
+    };
+
+    let summary = if added.is_empty() {
+        "nothing to add".to_string()
+    } else {
+        format!("added {}", added.join(", "))
+    };
+    Some(MergedFile {
+        content: content.into_bytes(),
+        summary,
+    })
+}
+
+/// Append `lines` to `content`, separated by a blank line
+fn append_lines(content: &str, lines: &[&str]) -> String {
+    let mut merged = content.trim_end_matches('\n').to_string();
+    if !merged.is_empty() {
+        merged.push_str("\n\n");
+    }
+    for line in lines {
+        merged.push_str(line);
+        merged.push('\n');
+    }
+    merged
+}
+
+fn merge_gitignore(existing: &str, new: &str) -> (String, Vec<String>) {
+    let present: Vec<&str> = existing.lines().map(str::trim).collect();
+    let missing: Vec<&str> = new
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter(|line| !present.contains(line))
+        .collect();
+
+    if missing.is_empty() {
+        return (existing.to_string(), Vec::new());
+    }
+    (
+        append_lines(existing, &missing),
+        vec![format!("{} ignore pattern(s)", missing.len())],
+    )
+}
+
+/// Package name of a requirements line (`fastapi[all]>=0.1 ; python_version>"3"` -> `fastapi`)
+fn requirement_name(line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') || line.starts_with('-') {
+        return None;
+    }
+    let end = line
+        .find(|c: char| "=<>!~;[ @".contains(c))
+        .unwrap_or(line.len());
+    Some(line[..end].to_lowercase().replace('_', "-"))
+}
+
+fn merge_requirements(existing: &str, new: &str) -> (String, Vec<String>) {
+    let present: Vec<String> = existing.lines().filter_map(requirement_name).collect();
+    let missing: Vec<&str> = new
+        .lines()
+        .filter(|line| requirement_name(line).is_some_and(|name| !present.contains(&name)))
+        .map(str::trim)
+        .collect();
+
+    if missing.is_empty() {
+        return (existing.to_string(), Vec::new());
+    }
+    let names: Vec<String> = missing.iter().filter_map(|l| requirement_name(l)).collect();
+    (
+        append_lines(existing, &missing),
+        vec![format!("requirements: {}", names.join(", "))],
+    )
+}
+
+fn merge_package_json(existing: &str, new: &str) -> Option<(String, Vec<String>)> {
+    let mut merged: JsonValue = serde_json::from_str(existing).ok()?;
+    let template: JsonValue = serde_json::from_str(new).ok()?;
+    let target = merged.as_object_mut()?;
+
+    let mut added = Vec::new();
+    for section in PACKAGE_JSON_SECTIONS {
+        let Some(JsonValue::Object(entries)) = template.get(*section) else {
+            continue;
+        };
+        let existing_section = target
+            .entry(section.to_string())
+            .or_insert_with(|| JsonValue::Object(Default::default()));
+        let Some(existing_section) = existing_section.as_object_mut() else {
+            continue;
+        };
+
+        let mut names = Vec::new();
+        for (name, value) in entries {
+            if !existing_section.contains_key(name) {
+                existing_section.insert(name.clone(), value.clone());
+                names.push(name.as_str());
+            }
+        }
+        if !names.is_empty() {
+            added.push(format!("{}: {}", section, names.join(", ")));
+        }
+    }
+
+    if added.is_empty() {
+        return Some((existing.to_string(), added));
+    }
+    let mut content = serde_json::to_string_pretty(&merged).ok()?;
+    content.push('\n');
+    Some((content, added))
+}
+
+/// Names of the entries in a config's top-level `workers:` list
+fn worker_names(config: &YamlValue) -> Option<Vec<String>> {
+    let workers = config.get("workers")?.as_sequence()?;
+    Some(
+        workers
+            .iter()
+            .filter_map(|w| w.get("name")?.as_str().map(String::from))
+            .collect(),
+    )
+}
+
+/// Line range of the top-level `workers:` block, and the indentation of its items
+fn workers_block(lines: &[&str]) -> Option<(usize, usize, usize)> {
+    let start = lines.iter().position(|l| l.trim_end() == "workers:")? + 1;
+    let end = lines[start..]
+        .iter()
+        .position(|l| {
+            !l.is_empty() && !l.starts_with(' ') && !l.starts_with('#') && !l.starts_with('-')
+        })
+        .map_or(lines.len(), |offset| start + offset);
+    let indent = lines[start..end]
+        .iter()
+        .find_map(|l| {
+            l.trim_start()
+                .starts_with("- ")
+                .then(|| l.len() - l.trim_start().len())
+        })
+        .unwrap_or(2);
+    Some((start, end, indent))
+}
+
+/// Original text of each worker entry in a `workers:` block, keyed by worker name
+fn worker_entries<'a>(lines: &[&'a str]) -> Option<Vec<(String, Vec<&'a str>, usize)>> {
+    let (start, end, indent) = workers_block(lines)?;
+    let mut entries: Vec<(String, Vec<&str>, usize)> = Vec::new();
+
+    for line in &lines[start..end] {
+        let is_item =
+            line.len() - line.trim_start().len() == indent && line.trim_start().starts_with("- ");
+        if is_item {
+            let name = line
+                .trim_start()
+                .trim_start_matches("- ")
+                .strip_prefix("name:")
+                .map(|n| n.trim().trim_matches(['"', '\'']).to_string())
+                .unwrap_or_default();
+            entries.push((name, vec![line], indent));
+        } else if let Some(entry) = entries.last_mut() {
+            entry.1.push(line);
+        }
+    }
+
+    // Trailing blank lines separate entries; they are not part of them
+    for entry in &mut entries {
+        while entry.1.last().is_some_and(|l| l.trim().is_empty()) {
+            entry.1.pop();
+        }
+    }
+    Some(entries)
+}
+
+fn merge_engine_config(existing: &str, new: &str) -> Option<(String, Vec<String>)> {
+    let existing_config: YamlValue = serde_yaml::from_str(existing).ok()?;
+    let template_config: YamlValue = serde_yaml::from_str(new).ok()?;
+    let present = worker_names(&existing_config)?;
+    let wanted = worker_names(&template_config)?;
+
+    let missing: Vec<&String> = wanted.iter().filter(|n| !present.contains(n)).collect();
+    if missing.is_empty() {
+        return Some((existing.to_string(), Vec::new()));
+    }
+
+    let existing_lines: Vec<&str> = existing.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let (_, block_end, existing_indent) = workers_block(&existing_lines)?;
+    let entries = worker_entries(&new_lines)?;
+
+    // Insert after the last non-blank line of the existing workers block
+    let mut insert_at = block_end;
+    while insert_at > 0 && existing_lines[insert_at - 1].trim().is_empty() {
+        insert_at -= 1;
+    }
+
+    let mut inserted: Vec<String> = Vec::new();
+    for (_, lines, indent) in entries.iter().filter(|(n, _, _)| missing.contains(&n)) {
+        inserted.push(String::new());
+        for line in lines {
+            let reindented = if line.trim().is_empty() {
+                String::new()
+            } else if existing_indent >= *indent {
+                format!("{}{}", " ".repeat(existing_indent - indent), line)
+            } else {
+                line.chars().skip(indent - existing_indent).collect()
+            };
+            inserted.push(reindented);
+        }
+    }
+
+    let mut merged: Vec<String> = existing_lines[..insert_at]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+    merged.extend(inserted);
+    if insert_at < existing_lines.len() {
+        merged.push(String::new());
+        merged.extend(
+            existing_lines[insert_at..]
+                .iter()
+                .skip_while(|l| l.trim().is_empty())
+                .map(|l| l.to_string()),
+        );
+    }
+
+    let mut content = merged.join("\n");
+    content.push('\n');
+
+    // Only keep the text merge if it still parses to the expected workers
+    let check: YamlValue = serde_yaml::from_str(&content).ok()?;
+    let merged_names = worker_names(&check)?;
+    if !wanted.iter().all(|n| merged_names.contains(n)) {
+        return None;
+    }
+
+    let names: Vec<&str> = missing.iter().map(|n| n.as_str()).collect();
+    Some((content, vec![format!("workers: {}", names.join(", "))]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merge(dest: &str, existing: &str, new: &str) -> (String, String) {
+        let merged = merge_known_file(dest, existing.as_bytes(), new.as_bytes()).unwrap();
+        (String::from_utf8(merged.content).unwrap(), merged.summary)
+    }
+
+    #[test]
+    fn unknown_files_are_not_merged() {
+        assert!(merge_known_file("README.md", b"a", b"b").is_none());
+        assert!(merge_known_file("package.json", b"{not json", b"{}").is_none());
+        assert!(merge_known_file("workers/api/package.json", b"{}", b"{}").is_none());
+        assert!(merge_known_file("workers/api/config.yaml", b"a: 1", b"b: 2").is_none());
+    }
+
+    #[test]
+    fn gitignore_appends_missing_lines() {
+        let (content, summary) = merge(
+            ".gitignore",
+            "node_modules\n.env\n",
+            "# deps\nnode_modules\n__pycache__/\n.venv\n",
+        );
+        assert_eq!(content, "node_modules\n.env\n\n__pycache__/\n.venv\n");
+        assert_eq!(summary, "added 2 ignore pattern(s)");
+    }
+
+    #[test]
+    fn package_json_adds_missing_dependencies_and_scripts() {
+        let existing = r#"{
+  "name": "my-app",
+  "scripts": { "dev": "vite" },
+  "dependencies": { "iii-sdk": "^0.1.0" }
+}"#;
+        let new = r#"{
+  "name": "template",
+  "scripts": { "dev": "tsx watch", "build": "tsc" },
+  "dependencies": { "iii-sdk": "^0.2.0", "zod": "^3.0.0" },
+  "devDependencies": { "typescript": "^5.0.0" }
+}"#;
+
+        let (content, summary) = merge("package.json", existing, new);
+        let merged: JsonValue = serde_json::from_str(&content).unwrap();
+        assert_eq!(merged["name"], "my-app");
+        assert_eq!(merged["scripts"]["dev"], "vite");
+        assert_eq!(merged["scripts"]["build"], "tsc");
+        assert_eq!(merged["dependencies"]["iii-sdk"], "^0.1.0");
+        assert_eq!(merged["dependencies"]["zod"], "^3.0.0");
+        assert_eq!(merged["devDependencies"]["typescript"], "^5.0.0");
+        assert!(content.find("\"name\"").unwrap() < content.find("\"scripts\"").unwrap());
+        assert_eq!(
+            summary,
+            "added scripts: build, dependencies: zod, devDependencies: typescript"
+        );
+    }
+
+    #[test]
+    fn requirements_union_by_package_name() {
+        let (content, summary) = merge(
+            "requirements.txt",
+            "iii-sdk==0.1.0\n# pinned\nRequests>=2\n",
+            "iii_sdk>=0.2\nrequests\npydantic>=2\n",
+        );
+        assert_eq!(
+            content,
+            "iii-sdk==0.1.0\n# pinned\nRequests>=2\n\npydantic>=2\n"
+        );
+        assert_eq!(summary, "added requirements: pydantic");
+    }
+
+    #[test]
+    fn engine_config_appends_missing_workers() {
+        let existing = "# my config\nworkers:\n  - name: iii-queue\n    config:\n      adapter: custom\n\nport: 3111\n";
+        let new = "workers:\n  - name: iii-queue\n    config:\n      adapter: builtin\n\n  - name: iii-state\n    config:\n      store: memory\n";
+
+        let (content, summary) = merge("config.yaml", existing, new);
+        assert_eq!(
+            content,
+            "# my config\nworkers:\n  - name: iii-queue\n    config:\n      adapter: custom\n\n  - name: iii-state\n    config:\n      store: memory\n\nport: 3111\n"
+        );
+        assert_eq!(summary, "added workers: iii-state");
+    }
+
+    #[test]
+    fn engine_config_reindents_entries() {
+        let existing = "workers:\n- name: a\n";
+        let new = "workers:\n    - name: b\n      config: {}\n";
+
+        let (content, _) = merge("config.yaml", existing, new);
+        assert_eq!(content, "workers:\n- name: a\n\n- name: b\n  config: {}\n");
+    }
+
+    #[test]
+    fn nothing_to_add_keeps_existing_content() {
+        let (content, summary) = merge(".gitignore", "a\nb\n", "b\n");
+        assert_eq!(content, "a\nb\n");
+        assert_eq!(summary, "nothing to add");
+    }
+}
@@ -1,6 +1,16 @@
 //! Template manifest types and parsing
 
+use crate::runtime::check::Language;
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+
+/// Resolved values of a template's boolean options, keyed by option name
+pub type OptionValues = BTreeMap<String, bool>;
+
+/// Resolved values of a template's text variables, keyed by variable name
+pub type VariableValues = BTreeMap<String, String>;
 
 /// File patterns associated with each language
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -43,18 +53,23 @@ impl LanguageFiles {
 
     /// Check if a filename matches any pattern in a list
     fn matches_any(filename: &str, patterns: &[String]) -> bool {
-        patterns.iter().any(|pattern| {
-            if pattern.starts_with('*') {
-                // Suffix match: *.ts matches foo.ts
-                filename.ends_with(&pattern[1..])
-            } else if pattern.ends_with('*') {
-                // Prefix match: requirements* matches requirements.txt
-                filename.starts_with(&pattern[..pattern.len() - 1])
-            } else {
-                // Exact match
-                filename == pattern
-            }
-        })
+        patterns
+            .iter()
+            .any(|pattern| Self::matches_pattern(filename, pattern))
+    }
+
+    /// Check if a filename matches a single pattern
+    fn matches_pattern(filename: &str, pattern: &str) -> bool {
+        if let Some(suffix) = pattern.strip_prefix('*') {
+            // Suffix match: *.ts matches foo.ts
+            filename.ends_with(suffix)
+        } else if let Some(prefix) = pattern.strip_suffix('*') {
+            // Prefix match: requirements* matches requirements.txt
+            filename.starts_with(prefix)
+        } else {
+            // Exact match
+            filename == pattern
+        }
     }
 
     /// Determine which language(s) a file is associated with
@@ -88,7 +103,8 @@ impl LanguageFiles {
 }
 
 /// Which language a file is associated with
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum FileLanguage {
     Common, // Always included
     Python,
@@ -98,15 +114,307 @@ pub enum FileLanguage {
     Rust,
 }
 
+impl FileLanguage {
+    /// Name as written in `language_files` and `files` entries
+    pub fn name(&self) -> &'static str {
+        match self {
+            FileLanguage::Common => "common",
+            FileLanguage::Python => "python",
+            FileLanguage::TypeScript => "typescript",
+            FileLanguage::JavaScript => "javascript",
+            FileLanguage::Node => "node",
+            FileLanguage::Rust => "rust",
+        }
+    }
+}
+
+/// An entry in a template's `files` list
+///
+/// Written either as a plain path (`src/worker.ts`) or as an object that renames the
+/// file and/or pins its language: `{ path: scripts/setup.sh, dest: setup.sh, language: python }`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "TemplateFileEntry", into = "TemplateFileEntry")]
+pub struct TemplateFile {
+    /// Source path relative to the template directory
+    pub path: String,
+
+    /// Destination path in the generated project (defaults to path if not specified)
+    pub dest: Option<String>,
+
+    /// Language that gates this file, overriding the `language_files` patterns
+    pub language: Option<FileLanguage>,
+}
+
+impl TemplateFile {
+    /// Create a plain entry that keeps its path and uses pattern-based classification
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            dest: None,
+            language: None,
+        }
+    }
+
+    /// Get the destination path (falls back to path if dest not specified)
+    pub fn destination(&self) -> &str {
+        self.dest.as_deref().unwrap_or(&self.path)
+    }
+
+    /// Determine the file's language: the explicit override, else the destination's pattern
+    pub fn language(&self, language_files: &LanguageFiles) -> Option<FileLanguage> {
+        self.language
+            .or_else(|| language_files.get_language_for_file(self.destination()))
+    }
+}
+
+/// On-disk form of a `files` entry: plain string or detailed object
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum TemplateFileEntry {
+    Path(String),
+    Detailed {
+        path: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dest: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        language: Option<FileLanguage>,
+    },
+}
+
+impl TryFrom<TemplateFileEntry> for TemplateFile {
+    type Error = String;
+
+    fn try_from(entry: TemplateFileEntry) -> Result<Self, Self::Error> {
+        let file = match entry {
+            TemplateFileEntry::Path(path) => TemplateFile::new(path),
+            TemplateFileEntry::Detailed {
+                path,
+                dest,
+                language,
+            } => TemplateFile {
+                path,
+                dest,
+                language,
+            },
+        };
+        // Manifests may come from a remote registry; their files must stay inside the project
+        for path in std::iter::once(&file.path).chain(&file.dest) {
+            if !is_project_relative(path) {
+                return Err(format!("file path must be inside the project: {}", path));
+            }
+        }
+        Ok(file)
+    }
+}
+
+impl From<TemplateFile> for TemplateFileEntry {
+    fn from(file: TemplateFile) -> Self {
+        if file.dest.is_none() && file.language.is_none() {
+            TemplateFileEntry::Path(file.path)
+        } else {
+            TemplateFileEntry::Detailed {
+                path: file.path,
+                dest: file.dest,
+                language: file.language,
+            }
+        }
+    }
+}
+
+/// Whether a path is relative and never climbs out of the directory it is joined onto
+pub fn is_project_relative(path: &str) -> bool {
+    Path::new(path)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Check if a template file path matches a path pattern
+///
+/// Patterns ending in `/` match every file under that directory, patterns containing
+/// a `/` are matched against the full path, and anything else is matched against the
+/// filename only (same rules as `language_files`).
+pub fn path_matches(file_path: &str, pattern: &str) -> bool {
+    if pattern.ends_with('/') {
+        return file_path.starts_with(pattern);
+    }
+    if pattern.contains('/') {
+        return LanguageFiles::matches_pattern(file_path, pattern);
+    }
+    let filename = file_path.rsplit('/').next().unwrap_or(file_path);
+    LanguageFiles::matches_pattern(filename, pattern)
+}
+
+/// A boolean option declared by a template (e.g. "include observability")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateOption {
+    /// Option name used on the command line (`--option name=true`)
+    pub name: String,
+
+    /// Human-readable description shown when prompting
+    #[serde(default)]
+    pub description: String,
+
+    /// Value used when the option is not answered explicitly
+    #[serde(default)]
+    pub default: bool,
+
+    /// File patterns that are only copied when this option is enabled
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+impl TemplateOption {
+    /// Label shown in prompts (falls back to the option name)
+    pub fn label(&self) -> &str {
+        if self.description.is_empty() {
+            &self.name
+        } else {
+            &self.description
+        }
+    }
+}
+
+/// Parse a `name=value` option assignment (a bare `name` means `true`)
+pub fn parse_option_assignment(assignment: &str) -> Result<(String, bool)> {
+    let (name, value) = match assignment.split_once('=') {
+        Some((name, value)) => (name.trim(), value.trim()),
+        None => (assignment.trim(), "true"),
+    };
+
+    if name.is_empty() {
+        anyhow::bail!("Invalid option '{}': missing option name", assignment);
+    }
+
+    let value = match value.to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => true,
+        "false" | "no" | "off" | "0" => false,
+        _ => anyhow::bail!(
+            "Invalid value for option '{}': {} (expected true or false)",
+            name,
+            value
+        ),
+    };
+
+    Ok((name.to_string(), value))
+}
+
+/// A text value declared by a template and substituted for `{{name}}` in its files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    /// Variable name used in files (`{{name}}`) and on the command line (`--var name=value`)
+    pub name: String,
+
+    /// Human-readable description shown when prompting
+    #[serde(default)]
+    pub description: String,
+
+    /// Value used when the variable is not answered; without one it must be answered
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+impl TemplateVariable {
+    /// Label shown in prompts (falls back to the variable name)
+    pub fn label(&self) -> &str {
+        if self.description.is_empty() {
+            &self.name
+        } else {
+            &self.description
+        }
+    }
+}
+
+/// Parse a `name=value` variable assignment
+pub fn parse_variable_assignment(assignment: &str) -> Result<(String, String)> {
+    let Some((name, value)) = assignment.split_once('=') else {
+        anyhow::bail!("Invalid variable '{}': expected name=value", assignment);
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("Invalid variable '{}': missing variable name", assignment);
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// A setup command run after the project files are written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostCreateHook {
+    /// Shell command to run (e.g. `uv venv`, `chmod +x scripts/dev.sh`)
+    pub run: String,
+
+    /// Short explanation shown when asking the user for consent
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Working directory relative to the project root (defaults to the root)
+    #[serde(default)]
+    pub cwd: Option<String>,
+
+    /// Only run when one of these languages is selected (`node` matches JS or TS)
+    #[serde(default)]
+    pub languages: Vec<String>,
+
+    /// Only run when all of these template options are enabled
+    #[serde(default)]
+    pub options: Vec<String>,
+
+    /// When true, a failing hook aborts project creation instead of warning
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl PostCreateHook {
+    /// Check whether the hook's language and option conditions are satisfied
+    pub fn applies_to(&self, selected_languages: &[Language], options: &OptionValues) -> bool {
+        let language_ok = self.languages.is_empty()
+            || self.languages.iter().any(|name| {
+                if name.eq_ignore_ascii_case("node") {
+                    selected_languages
+                        .iter()
+                        .any(|l| matches!(l, Language::TypeScript | Language::JavaScript))
+                } else {
+                    Language::parse(name).is_some_and(|l| selected_languages.contains(&l))
+                }
+            });
+
+        let options_ok = self
+            .options
+            .iter()
+            .all(|name| options.get(name).copied().unwrap_or(false));
+
+        language_ok && options_ok
+    }
+}
+
 /// A shared file from the root templates directory that gets bundled into every template
+///
+/// The source may also be a directory, in which case every file under it is bundled
+/// below `dest`. Conditions restrict which templates receive the entry and when the
+/// bundled files are copied into a generated project.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SharedFile {
-    /// Source path relative to templates/ directory
+    /// Source path relative to templates/ directory (file or directory)
     pub source: String,
 
     /// Destination path in each template (defaults to source if not specified)
     #[serde(default)]
     pub dest: Option<String>,
+
+    /// Language that gates the shared file(s), overriding the `language_files` patterns
+    #[serde(default)]
+    pub language: Option<FileLanguage>,
+
+    /// Template options that must all be enabled; templates that don't declare them skip the entry
+    #[serde(default)]
+    pub options: Vec<String>,
+
+    /// Only bundle into these templates (empty means every template)
+    #[serde(default)]
+    pub templates: Vec<String>,
+
+    /// Never bundle into these templates
+    #[serde(default)]
+    pub exclude_templates: Vec<String>,
 }
 
 impl SharedFile {
@@ -114,6 +422,16 @@ impl SharedFile {
     pub fn destination(&self) -> &str {
         self.dest.as_deref().unwrap_or(&self.source)
     }
+
+    /// Check the template opt-in/opt-out lists and that the template declares the gating options
+    pub fn applies_to_template(&self, template_name: &str, manifest: &TemplateManifest) -> bool {
+        let opted_in =
+            self.templates.is_empty() || self.templates.iter().any(|t| t == template_name);
+        let opted_out = self.exclude_templates.iter().any(|t| t == template_name);
+        let options_declared = self.options.iter().all(|o| manifest.option(o).is_some());
+
+        opted_in && !opted_out && options_declared
+    }
 }
 
 /// Root template manifest (templates/template.yaml)
@@ -133,6 +451,15 @@ pub struct RootManifest {
     pub shared_files: Vec<SharedFile>,
 }
 
+impl RootManifest {
+    /// Global language files merged with a template's own overrides
+    pub fn language_files_for(&self, manifest: &TemplateManifest) -> LanguageFiles {
+        let mut merged = self.language_files.clone();
+        merged.merge(&manifest.language_files);
+        merged
+    }
+}
+
 /// Per-template manifest (templates/<name>/template.yaml)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemplateManifest {
@@ -145,6 +472,15 @@ pub struct TemplateManifest {
     /// Semver version for CLI compatibility checking
     pub version: String,
 
+    /// Base template (directory name in the same registry) to inherit from
+    /// Resolved when zips are built, so published manifests never carry it.
+    #[serde(default)]
+    pub extends: Option<String>,
+
+    /// Free-form labels used to group and search templates (e.g. `python`, `ai`)
+    #[serde(default)]
+    pub tags: Vec<String>,
+
     /// Minimum iii engine version required (checked via `iii --version`)
     /// When set, the CLI will hard-block if the installed iii version is too old.
     #[serde(default)]
@@ -163,13 +499,27 @@ pub struct TemplateManifest {
     #[serde(default, alias = "treat_required_as_suggested")]
     pub treat_required_as_included: bool,
 
-    /// Explicit list of files to copy
-    pub files: Vec<String>,
+    /// Explicit list of files to copy (may be empty when inherited via `extends`)
+    /// Entries are plain paths or `{ path, dest, language }` objects
+    #[serde(default)]
+    pub files: Vec<TemplateFile>,
 
     /// Template-specific language file overrides (merged with root)
     #[serde(default)]
     pub language_files: LanguageFiles,
 
+    /// Boolean options that gate files and config sections
+    #[serde(default)]
+    pub options: Vec<TemplateOption>,
+
+    /// Text values substituted into the template's files
+    #[serde(default)]
+    pub variables: Vec<TemplateVariable>,
+
+    /// Setup commands run after the files are written (with user consent)
+    #[serde(default)]
+    pub post_create: Vec<PostCreateHook>,
+
     /// Post-creation steps shown to the user (after the auto-generated `cd` step)
     #[serde(default)]
     pub next_steps: Vec<String>,
@@ -199,6 +549,158 @@ impl TemplateManifest {
             Vec::new()
         }
     }
+
+    /// Fill in everything this manifest inherits from a base template
+    ///
+    /// Files, language patterns, options and hooks are combined with this manifest's
+    /// entries taking precedence. `requires`, `optional`, `next_steps` and
+    /// `min_iii_version` are inherited only when this manifest leaves them unset.
+    pub fn inherit_from(&mut self, base: &TemplateManifest) {
+        // Entries with the same destination replace the inherited ones in place
+        let mut files = base.files.clone();
+        for file in self.files.drain(..) {
+            match files
+                .iter_mut()
+                .find(|f| f.destination() == file.destination())
+            {
+                Some(existing) => *existing = file,
+                None => files.push(file),
+            }
+        }
+        self.files = files;
+
+        let mut language_files = base.language_files.clone();
+        language_files.merge(&self.language_files);
+        self.language_files = language_files;
+
+        if self.tags.is_empty() {
+            self.tags = base.tags.clone();
+        }
+        if self.min_iii_version.is_none() {
+            self.min_iii_version = base.min_iii_version.clone();
+        }
+        if self.requires.is_empty() {
+            self.requires = base.requires.clone();
+        }
+        if self.optional.is_empty() {
+            self.optional = base.optional.clone();
+        }
+        self.treat_required_as_included |= base.treat_required_as_included;
+        if self.next_steps.is_empty() {
+            self.next_steps = base.next_steps.clone();
+        }
+
+        let mut options: Vec<TemplateOption> = base
+            .options
+            .iter()
+            .filter(|o| self.option(&o.name).is_none())
+            .cloned()
+            .collect();
+        options.append(&mut self.options);
+        self.options = options;
+
+        let mut variables: Vec<TemplateVariable> = base
+            .variables
+            .iter()
+            .filter(|v| self.variable(&v.name).is_none())
+            .cloned()
+            .collect();
+        variables.append(&mut self.variables);
+        self.variables = variables;
+
+        let mut post_create = base.post_create.clone();
+        post_create.append(&mut self.post_create);
+        self.post_create = post_create;
+
+        self.extends = base.extends.clone();
+    }
+
+    /// Look up a declared option by name
+    pub fn option(&self, name: &str) -> Option<&TemplateOption> {
+        self.options.iter().find(|o| o.name == name)
+    }
+
+    /// Combine explicit answers with option defaults, rejecting unknown option names
+    pub fn resolve_options(&self, answers: &OptionValues) -> Result<OptionValues> {
+        if let Some(unknown) = answers.keys().find(|name| self.option(name).is_none()) {
+            let available: Vec<&str> = self.options.iter().map(|o| o.name.as_str()).collect();
+            anyhow::bail!(
+                "Unknown option '{}'. Available options: {}",
+                unknown,
+                if available.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    available.join(", ")
+                }
+            );
+        }
+
+        Ok(self
+            .options
+            .iter()
+            .map(|o| {
+                let value = answers.get(&o.name).copied().unwrap_or(o.default);
+                (o.name.clone(), value)
+            })
+            .collect())
+    }
+
+    /// Look up a declared variable by name
+    pub fn variable(&self, name: &str) -> Option<&TemplateVariable> {
+        self.variables.iter().find(|v| v.name == name)
+    }
+
+    /// Combine explicit answers with variable defaults, rejecting unknown names and
+    /// variables left without a value
+    pub fn resolve_variables(&self, answers: &VariableValues) -> Result<VariableValues> {
+        if let Some(unknown) = answers.keys().find(|name| self.variable(name).is_none()) {
+            let available: Vec<&str> = self.variables.iter().map(|v| v.name.as_str()).collect();
+            anyhow::bail!(
+                "Unknown variable '{}'. Available variables: {}",
+                unknown,
+                if available.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    available.join(", ")
+                }
+            );
+        }
+
+        self.variables
+            .iter()
+            .map(|v| {
+                let value = answers
+                    .get(&v.name)
+                    .or(v.default.as_ref())
+                    .cloned()
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "No value for variable '{}'. Pass it with --var {}=VALUE",
+                            v.name,
+                            v.name
+                        )
+                    })?;
+                Ok((v.name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Check that every option gating this file is enabled
+    pub fn options_allow_file(&self, file_path: &str, values: &OptionValues) -> bool {
+        self.disabled_option_for(file_path, values).is_none()
+    }
+
+    /// First option gating this file that is disabled, if any
+    pub fn disabled_option_for(
+        &self,
+        file_path: &str,
+        values: &OptionValues,
+    ) -> Option<&TemplateOption> {
+        self.options
+            .iter()
+            .filter(|o| o.files.iter().any(|p| path_matches(file_path, p)))
+            .find(|o| !values.get(&o.name).copied().unwrap_or(o.default))
+    }
 }
 
 #[cfg(test)]
@@ -215,6 +717,29 @@ mod tests {
         yaml
     }
 
+    #[test]
+    fn file_paths_must_stay_inside_the_project() {
+        for entry in [
+            "'/etc/passwd'",
+            "'../outside.txt'",
+            "{ path: README.md, dest: ../../.bashrc }",
+            "{ path: README.md, dest: /etc/x }",
+        ] {
+            let yaml = format!(
+                "name: T\ndescription: D\nversion: '0.1.0'\nfiles:\n  - {}\n",
+                entry
+            );
+            let err = serde_yaml::from_str::<TemplateManifest>(&yaml).unwrap_err();
+            assert!(err.to_string().contains("inside the project"), "{}", err);
+        }
+
+        let manifest: TemplateManifest = serde_yaml::from_str(
+            "name: T\ndescription: D\nversion: '0.1.0'\nfiles:\n  - ./src/a.ts\n  - { path: b.sh, dest: scripts/b.sh }\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.files.len(), 2);
+    }
+
     #[test]
     fn next_steps_defaults_to_empty_when_absent() {
         let manifest: TemplateManifest =
@@ -241,6 +766,267 @@ mod tests {
             "next_steps:\n  - Step one\n  - Step two\n  - Step three\n",
         )))
         .unwrap();
-        assert_eq!(manifest.next_steps, vec!["Step one", "Step two", "Step three"]);
+        assert_eq!(
+            manifest.next_steps,
+            vec!["Step one", "Step two", "Step three"]
+        );
+    }
+
+    fn manifest_with_options() -> TemplateManifest {
+        serde_yaml::from_str(&minimal_manifest_yaml(Some(
+            "options:\n  - name: docker\n    description: Add Docker support\n    files:\n      - Dockerfile\n      - docker/\n  - name: observability\n    default: true\n    files:\n      - workers/otel/*\n",
+        )))
+        .unwrap()
+    }
+
+    #[test]
+    fn options_default_to_empty_when_absent() {
+        let manifest: TemplateManifest =
+            serde_yaml::from_str(&minimal_manifest_yaml(None)).unwrap();
+        assert!(manifest.options.is_empty());
+    }
+
+    #[test]
+    fn resolve_options_fills_defaults() {
+        let manifest = manifest_with_options();
+        let mut answers = OptionValues::new();
+        answers.insert("docker".to_string(), true);
+
+        let resolved = manifest.resolve_options(&answers).unwrap();
+        assert_eq!(resolved.get("docker"), Some(&true));
+        assert_eq!(resolved.get("observability"), Some(&true));
+    }
+
+    #[test]
+    fn resolve_options_rejects_unknown_names() {
+        let manifest = manifest_with_options();
+        let mut answers = OptionValues::new();
+        answers.insert("redis".to_string(), true);
+
+        let err = manifest.resolve_options(&answers).unwrap_err().to_string();
+        assert!(err.contains("redis"));
+        assert!(err.contains("docker"));
+    }
+
+    #[test]
+    fn resolve_variables_fills_defaults_and_requires_the_rest() {
+        let manifest: TemplateManifest = serde_yaml::from_str(&format!(
+            "{}{}",
+            minimal_manifest_yaml(None),
+            "variables:\n  - name: service\n    description: Service name\n  - name: port\n    default: '3000'\n",
+        ))
+        .unwrap();
+
+        let answers = VariableValues::from([("service".to_string(), "billing".to_string())]);
+        let resolved = manifest.resolve_variables(&answers).unwrap();
+        assert_eq!(resolved["service"], "billing");
+        assert_eq!(resolved["port"], "3000");
+
+        let err = manifest
+            .resolve_variables(&VariableValues::new())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("--var service=VALUE"));
+
+        let unknown = VariableValues::from([("region".to_string(), "eu".to_string())]);
+        assert!(manifest.resolve_variables(&unknown).is_err());
+    }
+
+    #[test]
+    fn options_gate_matching_files() {
+        let manifest = manifest_with_options();
+        let mut values = OptionValues::new();
+        values.insert("docker".to_string(), false);
+        values.insert("observability".to_string(), true);
+
+        assert!(!manifest.options_allow_file("Dockerfile", &values));
+        assert!(!manifest.options_allow_file("docker/entrypoint.sh", &values));
+        assert!(manifest.options_allow_file("workers/otel/config.yaml", &values));
+        assert!(manifest.options_allow_file("README.md", &values));
+
+        values.insert("docker".to_string(), true);
+        assert!(manifest.options_allow_file("workers/api/Dockerfile", &values));
+    }
+
+    #[test]
+    fn post_create_hooks_parse_from_yaml() {
+        let manifest: TemplateManifest = serde_yaml::from_str(&minimal_manifest_yaml(Some(
+            "post_create:\n  - run: uv venv\n    cwd: workers/math-worker\n    languages: [python]\n    required: true\n  - run: chmod +x dev.sh\n",
+        )))
+        .unwrap();
+        assert_eq!(manifest.post_create.len(), 2);
+        assert_eq!(manifest.post_create[0].run, "uv venv");
+        assert_eq!(
+            manifest.post_create[0].cwd.as_deref(),
+            Some("workers/math-worker")
+        );
+        assert!(manifest.post_create[0].required);
+        assert!(!manifest.post_create[1].required);
+    }
+
+    #[test]
+    fn post_create_hook_conditions() {
+        let hook: PostCreateHook = serde_yaml::from_str(
+            "run: npm run build\nlanguages: [node, python]\noptions: [docker]\n",
+        )
+        .unwrap();
+        let mut options = OptionValues::new();
+        options.insert("docker".to_string(), true);
+
+        assert!(hook.applies_to(&[Language::TypeScript], &options));
+        assert!(hook.applies_to(&[Language::Python], &options));
+        assert!(!hook.applies_to(&[Language::Rust], &options));
+
+        options.insert("docker".to_string(), false);
+        assert!(!hook.applies_to(&[Language::TypeScript], &options));
+    }
+
+    #[test]
+    fn inherit_from_merges_base_manifest() {
+        let base: TemplateManifest = serde_yaml::from_str(
+            "name: Base\ndescription: Worker skeleton\nversion: '0.1.0'\ntags: [node]\nmin_iii_version: '0.11.0'\nrequires: [typescript]\nfiles:\n  - package.json\n  - tsconfig.json\nlanguage_files:\n  common: ['*.md']\nnext_steps:\n  - Base step\n",
+        )
+        .unwrap();
+        let mut child: TemplateManifest = serde_yaml::from_str(
+            "name: Child\ndescription: Uses the skeleton\nversion: '0.2.0'\nextends: base\nfiles:\n  - tsconfig.json\n  - src/worker.ts\nlanguage_files:\n  typescript: ['*.ts']\n",
+        )
+        .unwrap();
+
+        child.inherit_from(&base);
+
+        assert_eq!(child.name, "Child");
+        assert_eq!(child.version, "0.2.0");
+        assert_eq!(child.extends, None);
+        let paths: Vec<&str> = child.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["package.json", "tsconfig.json", "src/worker.ts"]
+        );
+        assert_eq!(child.requires, vec!["typescript"]);
+        assert_eq!(child.min_iii_version.as_deref(), Some("0.11.0"));
+        assert_eq!(child.tags, vec!["node"]);
+        assert_eq!(child.next_steps, vec!["Base step"]);
+        assert_eq!(child.language_files.common, vec!["*.md"]);
+        assert_eq!(child.language_files.typescript, vec!["*.ts"]);
+    }
+
+    #[test]
+    fn inherit_from_keeps_child_overrides() {
+        let base: TemplateManifest = serde_yaml::from_str(&minimal_manifest_yaml(Some(
+            "requires: [python]\nnext_steps:\n  - Base step\noptions:\n  - name: docker\n",
+        )))
+        .unwrap();
+        let mut child: TemplateManifest = serde_yaml::from_str(&minimal_manifest_yaml(Some(
+            "requires: [rust]\nnext_steps:\n  - Child step\noptions:\n  - name: docker\n    default: true\n",
+        )))
+        .unwrap();
+
+        child.inherit_from(&base);
+
+        assert_eq!(child.requires, vec!["rust"]);
+        assert_eq!(child.next_steps, vec!["Child step"]);
+        assert_eq!(child.options.len(), 1);
+        assert!(child.options[0].default);
+    }
+
+    #[test]
+    fn files_accept_plain_and_detailed_entries() {
+        let manifest: TemplateManifest = serde_yaml::from_str(
+            "name: Test\ndescription: A test template\nversion: '0.1.0'\nfiles:\n  - README.md\n  - path: scripts/setup.sh\n    dest: setup.sh\n    language: python\n",
+        )
+        .unwrap();
+
+        assert_eq!(manifest.files[0], TemplateFile::new("README.md"));
+        assert_eq!(manifest.files[1].path, "scripts/setup.sh");
+        assert_eq!(manifest.files[1].destination(), "setup.sh");
+        assert_eq!(manifest.files[1].language, Some(FileLanguage::Python));
+    }
+
+    #[test]
+    fn files_round_trip_through_yaml() {
+        let files = vec![
+            TemplateFile::new("README.md"),
+            TemplateFile {
+                path: "scripts/setup.sh".to_string(),
+                dest: Some("setup.sh".to_string()),
+                language: Some(FileLanguage::Node),
+            },
+        ];
+
+        let yaml = serde_yaml::to_string(&files).unwrap();
+        assert!(yaml.contains("- README.md"));
+        assert!(yaml.contains("language: node"));
+
+        let parsed: Vec<TemplateFile> = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, files);
+    }
+
+    #[test]
+    fn file_language_override_beats_patterns() {
+        let lf = LanguageFiles {
+            common: vec!["*.sh".to_string()],
+            ..Default::default()
+        };
+        let plain = TemplateFile::new("scripts/setup.sh");
+        let pinned = TemplateFile {
+            language: Some(FileLanguage::Python),
+            ..TemplateFile::new("scripts/setup.sh")
+        };
+
+        assert_eq!(plain.language(&lf), Some(FileLanguage::Common));
+        assert_eq!(pinned.language(&lf), Some(FileLanguage::Python));
+    }
+
+    #[test]
+    fn shared_file_template_conditions() {
+        let manifest = manifest_with_options();
+        let shared: SharedFile = serde_yaml::from_str(
+            "source: default-dockerignore\ndest: .dockerignore\nexclude_templates: [starter]\noptions: [docker]\n",
+        )
+        .unwrap();
+
+        assert!(shared.applies_to_template("quickstart", &manifest));
+        assert!(!shared.applies_to_template("starter", &manifest));
+
+        let undeclared: TemplateManifest =
+            serde_yaml::from_str(&minimal_manifest_yaml(None)).unwrap();
+        assert!(!shared.applies_to_template("quickstart", &undeclared));
+
+        let opt_in: SharedFile =
+            serde_yaml::from_str("source: compose.yaml\ntemplates: [quickstart]\n").unwrap();
+        assert!(opt_in.applies_to_template("quickstart", &undeclared));
+        assert!(!opt_in.applies_to_template("starter", &undeclared));
+    }
+
+    #[test]
+    fn parse_option_assignment_accepts_common_forms() {
+        assert_eq!(
+            parse_option_assignment("docker=true").unwrap(),
+            ("docker".to_string(), true)
+        );
+        assert_eq!(
+            parse_option_assignment("docker=no").unwrap(),
+            ("docker".to_string(), false)
+        );
+        assert_eq!(
+            parse_option_assignment("docker").unwrap(),
+            ("docker".to_string(), true)
+        );
+        assert!(parse_option_assignment("docker=maybe").is_err());
+        assert!(parse_option_assignment("=true").is_err());
+    }
+
+    #[test]
+    fn parse_variable_assignment_keeps_the_value_verbatim() {
+        assert_eq!(
+            parse_variable_assignment("greeting=Hello, a=b").unwrap(),
+            ("greeting".to_string(), "Hello, a=b".to_string())
+        );
+        assert_eq!(
+            parse_variable_assignment("empty=").unwrap(),
+            ("empty".to_string(), String::new())
+        );
+        assert!(parse_variable_assignment("service").is_err());
+        assert!(parse_variable_assignment("=x").is_err());
     }
 }
@@ -3,22 +3,43 @@
 //! This module provides:
 //! - Template manifest types (RootManifest, TemplateManifest)
 //! - Template fetching from remote URLs or local directories
+//! - Listing the templates in a registry and describing a single template
 //! - Template copying with language-based filtering
+//! - Merging template output into existing well-known files
+//! - Post-create hook execution
+//! - Worker extraction for adding workers to existing projects
 //! - Version compatibility checking
+//! - Validation of local template registries
+//! - Extracting templates from existing projects
 
+pub mod authoring;
+pub mod catalog;
 pub mod copier;
+pub mod extract;
 pub mod fetcher;
+pub mod hooks;
+pub mod info;
+pub mod known_files;
 pub mod manifest;
+pub mod validate;
 pub mod version;
+pub mod worker;
 
 use crate::product::ProductConfig;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::path::PathBuf;
 
+pub use catalog::{list_templates, TemplateSummary};
 pub use copier::copy_template;
+pub use extract::{run_extract, ExtractArgs};
 pub use fetcher::{TemplateFetcher, TemplateSource};
-pub use manifest::{LanguageFiles, RootManifest, SharedFile, TemplateManifest};
+pub use info::{show_template_info, InfoArgs};
+pub use manifest::{
+    LanguageFiles, OptionValues, PostCreateHook, RootManifest, SharedFile, TemplateFile,
+    TemplateManifest, TemplateOption,
+};
+pub use validate::{validate_registry, validate_templates};
 pub use version::check_compatibility;
 
 /// Build zip files for all templates in a directory
@@ -42,8 +63,8 @@ pub async fn build_zips<C: ProductConfig>(
     // Read root manifest to get template list
     let manifest_content = std::fs::read_to_string(&manifest_path)
         .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
-    let root_manifest: manifest::RootManifest = serde_yaml::from_str(&manifest_content)
-        .context("Failed to parse root template.yaml")?;
+    let root_manifest: manifest::RootManifest =
+        serde_yaml::from_str(&manifest_content).context("Failed to parse root template.yaml")?;
 
     println!(
         "{}",
@@ -76,6 +97,15 @@ pub async fn build_zips<C: ProductConfig>(
                 let zip_path = dir.join(format!("{}.zip", template_name));
                 std::fs::write(&zip_path, &zip_bytes)
                     .with_context(|| format!("Failed to write {}", zip_path.display()))?;
+                // Keep a copy per version so `upgrade` can fetch the version a project
+                // was created from as its merge base
+                let (manifest, _) =
+                    fetcher::TemplateFetcher::resolve_local_manifest(&dir, template_name)?;
+                let versioned =
+                    fetcher::TemplateFetcher::versioned_name(template_name, &manifest.version);
+                let versioned_path = dir.join(format!("{}.zip", versioned));
+                std::fs::write(&versioned_path, &zip_bytes)
+                    .with_context(|| format!("Failed to write {}", versioned_path.display()))?;
                 println!(" {} ({} bytes)", "done".green(), zip_bytes.len());
                 built += 1;
             }
@@ -0,0 +1,549 @@
+//! Linting a local template registry
+//!
+//! Runs the same checks the repository's own templates are held to and reports every
+//! problem found as a [`Diagnostic`], so template authors can fix them in one pass.
+
+use super::fetcher::TemplateFetcher;
+use super::manifest::{RootManifest, TemplateManifest};
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Zips smaller than this almost certainly lost their files
+const MIN_ZIP_SIZE: usize = 100;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Which check produced a diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Check {
+    /// The root template.yaml is missing, unparsable or lists no templates
+    RootManifest,
+    /// A template manifest is missing, unparsable or has empty required fields
+    Manifest,
+    /// A file listed in a template manifest does not exist
+    MissingFile,
+    /// A shared file referenced from the root manifest does not exist
+    SharedSource,
+    /// A listed file matches no `language_files` pattern and has no language override
+    LanguagePattern,
+    /// Building the template zip failed or produced a suspiciously small archive
+    ZipBuild,
+    /// Files in one template depend on different iii SDK versions
+    SdkVersion,
+}
+
+impl Check {
+    /// Identifier used in human and JSON output
+    pub fn label(&self) -> &'static str {
+        match self {
+            Check::RootManifest => "root-manifest",
+            Check::Manifest => "manifest",
+            Check::MissingFile => "missing-file",
+            Check::SharedSource => "shared-source",
+            Check::LanguagePattern => "language-pattern",
+            Check::ZipBuild => "zip-build",
+            Check::SdkVersion => "sdk-version",
+        }
+    }
+}
+
+/// A single problem found in a registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub check: Check,
+    /// Template the problem belongs to (`None` for registry-wide problems)
+    pub template: Option<String>,
+    /// File the problem belongs to, relative to the template (or registry) directory
+    pub file: Option<String>,
+    pub message: String,
+}
+
+/// Result of validating a registry
+#[derive(Debug, Default, Serialize)]
+pub struct ValidationReport {
+    /// Number of templates listed in the root manifest
+    pub templates_checked: usize,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    pub fn errors(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors() > 0
+    }
+
+    /// Diagnostics produced by one check
+    pub fn for_check(&self, check: Check) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(move |d| d.check == check)
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    }
+
+    fn push(
+        &mut self,
+        severity: Severity,
+        check: Check,
+        template: Option<&str>,
+        file: Option<&str>,
+        message: impl Into<String>,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            check,
+            template: template.map(str::to_string),
+            file: file.map(str::to_string),
+            message: message.into(),
+        });
+    }
+
+    fn error(&mut self, check: Check, template: Option<&str>, file: Option<&str>, message: String) {
+        self.push(Severity::Error, check, template, file, message);
+    }
+}
+
+/// Validate every template in a local registry directory
+pub fn validate_registry(dir: &Path) -> ValidationReport {
+    let mut report = ValidationReport::default();
+
+    let manifest_path = dir.join("template.yaml");
+    let root: RootManifest = match std::fs::read_to_string(&manifest_path)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| serde_yaml::from_str(&content).map_err(anyhow::Error::from))
+    {
+        Ok(root) => root,
+        Err(e) => {
+            report.error(
+                Check::RootManifest,
+                None,
+                Some("template.yaml"),
+                format!("Failed to load {}: {}", manifest_path.display(), e),
+            );
+            return report;
+        }
+    };
+
+    if root.templates.is_empty() {
+        report.error(
+            Check::RootManifest,
+            None,
+            Some("template.yaml"),
+            "templates list is empty".to_string(),
+        );
+    }
+
+    for shared in &root.shared_files {
+        if !dir.join(&shared.source).exists() {
+            report.error(
+                Check::SharedSource,
+                None,
+                Some(&shared.source),
+                format!("shared file source does not exist: {}", shared.source),
+            );
+        }
+    }
+
+    report.templates_checked = root.templates.len();
+    for name in &root.templates {
+        validate_template(dir, &root, name, &mut report);
+    }
+
+    report
+}
+
+fn validate_template(dir: &Path, root: &RootManifest, name: &str, report: &mut ValidationReport) {
+    let (manifest, source_dirs) = match TemplateFetcher::resolve_local_manifest(dir, name) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            report.error(
+                Check::Manifest,
+                Some(name),
+                Some("template.yaml"),
+                format!("{:#}", e),
+            );
+            return;
+        }
+    };
+
+    for (field, empty) in [
+        ("name", manifest.name.is_empty()),
+        ("description", manifest.description.is_empty()),
+        ("version", manifest.version.is_empty()),
+        ("files", manifest.files.is_empty()),
+    ] {
+        if empty {
+            report.error(
+                Check::Manifest,
+                Some(name),
+                Some("template.yaml"),
+                format!("{} is empty", field),
+            );
+        }
+    }
+
+    // Files provided through shared_files are bundled at zip build time
+    let shared_dests: HashSet<&str> = root.shared_files.iter().map(|s| s.destination()).collect();
+    let language_files = root.language_files_for(&manifest);
+
+    for file in &manifest.files {
+        if !shared_dests.contains(file.path.as_str())
+            && find_source(&source_dirs, &file.path).is_none()
+        {
+            report.error(
+                Check::MissingFile,
+                Some(name),
+                Some(&file.path),
+                format!("listed file does not exist: {}", file.path),
+            );
+        }
+
+        if file.language(&language_files).is_none() {
+            report.error(
+                Check::LanguagePattern,
+                Some(name),
+                Some(&file.path),
+                format!(
+                    "file '{}' does not match any language_files pattern",
+                    file.path
+                ),
+            );
+        }
+    }
+
+    match TemplateFetcher::build_local_zip(dir, name, &root.shared_files) {
+        Ok(zip) if zip.len() <= MIN_ZIP_SIZE => report.push(
+            Severity::Warning,
+            Check::ZipBuild,
+            Some(name),
+            None,
+            format!("zip is suspiciously small ({} bytes)", zip.len()),
+        ),
+        Ok(_) => {}
+        Err(e) => report.error(
+            Check::ZipBuild,
+            Some(name),
+            None,
+            format!("zip build failed: {:#}", e),
+        ),
+    }
+
+    check_sdk_versions(&manifest, &source_dirs, name, report);
+}
+
+/// First template directory in the `extends` chain that has the file
+fn find_source(source_dirs: &[PathBuf], path: &str) -> Option<PathBuf> {
+    source_dirs
+        .iter()
+        .map(|dir| dir.join(path))
+        .find(|full| full.exists())
+}
+
+fn check_sdk_versions(
+    manifest: &TemplateManifest,
+    source_dirs: &[PathBuf],
+    name: &str,
+    report: &mut ValidationReport,
+) {
+    let mut npm_versions: Vec<(String, String)> = Vec::new();
+    let mut cargo_versions: Vec<(String, String)> = Vec::new();
+    let mut pip_versions: Vec<(String, String)> = Vec::new();
+
+    for file in &manifest.files {
+        let Some(full) = find_source(source_dirs, &file.path) else {
+            continue;
+        };
+        let Ok(content) = std::fs::read_to_string(&full) else {
+            continue;
+        };
+
+        let dest = file.destination();
+        let basename = dest.rsplit('/').next().unwrap_or(dest);
+        let (versions, version) = match basename {
+            "package.json" => (&mut npm_versions, npm_sdk_version(&content)),
+            "Cargo.toml" => (&mut cargo_versions, cargo_sdk_version(&content)),
+            "requirements.txt" => (&mut pip_versions, pip_sdk_version(&content)),
+            _ => continue,
+        };
+        if let Some(version) = version {
+            versions.push((file.path.clone(), version));
+        }
+    }
+
+    for (ecosystem, versions) in [
+        ("npm", &npm_versions),
+        ("cargo", &cargo_versions),
+        ("pip", &pip_versions),
+    ] {
+        let Some((first_path, first)) = versions.first() else {
+            continue;
+        };
+        for (path, version) in &versions[1..] {
+            if version != first {
+                report.error(
+                    Check::SdkVersion,
+                    Some(name),
+                    Some(path),
+                    format!(
+                        "{} SDK version mismatch: {} has {} but {} has {}",
+                        ecosystem, first_path, first, path, version
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Extract the iii-sdk version from a package.json string
+fn npm_sdk_version(content: &str) -> Option<String> {
+    let v: serde_json::Value = serde_json::from_str(content).ok()?;
+    v.get("dependencies")?
+        .get("iii-sdk")?
+        .as_str()
+        .map(String::from)
+}
+
+/// Extract the iii-sdk version from a Cargo.toml string
+fn cargo_sdk_version(content: &str) -> Option<String> {
+    content.lines().map(str::trim).find_map(|line| {
+        let rest = line.strip_prefix("iii-sdk")?;
+        let (_, value) = rest.split_once('=')?;
+        Some(value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Extract the iii-sdk requirement from a requirements.txt string
+fn pip_sdk_version(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("iii-sdk"))
+        .map(String::from)
+}
+
+/// Validate a registry and print the diagnostics, failing when any error was found
+pub fn validate_templates(template_dir: &Path, json: bool) -> Result<()> {
+    let report = validate_registry(template_dir);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(template_dir, &report);
+    }
+
+    if report.has_errors() {
+        anyhow::bail!(
+            "Template validation failed with {} error{}",
+            report.errors(),
+            if report.errors() == 1 { "" } else { "s" }
+        );
+    }
+
+    Ok(())
+}
+
+fn print_report(template_dir: &Path, report: &ValidationReport) {
+    println!(
+        "{}",
+        format!("Validating templates in {}", template_dir.display())
+            .cyan()
+            .bold()
+    );
+    println!();
+
+    for diagnostic in &report.diagnostics {
+        let severity = match diagnostic.severity {
+            Severity::Error => "error".red().bold(),
+            Severity::Warning => "warning".yellow().bold(),
+        };
+        let location = match (&diagnostic.template, &diagnostic.file) {
+            (Some(template), Some(file)) => format!("{}/{}", template, file),
+            (Some(template), None) => template.clone(),
+            (None, Some(file)) => file.clone(),
+            (None, None) => String::new(),
+        };
+        println!(
+            "  {} {} {}  {}",
+            severity,
+            location.bold(),
+            diagnostic.message,
+            format!("[{}]", diagnostic.check.label()).dimmed()
+        );
+    }
+    if !report.diagnostics.is_empty() {
+        println!();
+    }
+
+    let summary = format!(
+        "Checked {} template{}: {} error{}, {} warning{}",
+        report.templates_checked,
+        if report.templates_checked == 1 {
+            ""
+        } else {
+            "s"
+        },
+        report.errors(),
+        if report.errors() == 1 { "" } else { "s" },
+        report.warnings(),
+        if report.warnings() == 1 { "" } else { "s" },
+    );
+    if report.has_errors() {
+        println!("{}", summary.red());
+    } else {
+        println!("{}", summary.green());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let full = dir.join(path);
+        std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+        std::fs::write(full, content).unwrap();
+    }
+
+    fn registry(template_yaml: &str) -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        write(
+            tmp.path(),
+            "template.yaml",
+            "templates: [api]\n\
+             language_files:\n  common: [README.md]\n  node: [package.json]\n  typescript: ['*.ts']\n",
+        );
+        write(tmp.path(), "api/template.yaml", template_yaml);
+        write(
+            tmp.path(),
+            "api/README.md",
+            "# API\n\nGenerated by a test.\n",
+        );
+        tmp
+    }
+
+    fn checks(report: &ValidationReport) -> Vec<Check> {
+        report.diagnostics.iter().map(|d| d.check).collect()
+    }
+
+    #[test]
+    fn valid_registry_has_no_diagnostics() {
+        let tmp = registry("name: API\ndescription: d\nversion: '1.0.0'\nfiles: [README.md]\n");
+        let report = validate_registry(tmp.path());
+        assert_eq!(report.templates_checked, 1);
+        assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+    }
+
+    #[test]
+    fn reports_missing_and_unmatched_files() {
+        let tmp = registry(
+            "name: API\ndescription: d\nversion: '1.0.0'\nfiles: [README.md, src/main.ts, notes.txt]\n",
+        );
+        write(tmp.path(), "api/notes.txt", "x");
+
+        let report = validate_registry(tmp.path());
+        let missing: Vec<_> = report.for_check(Check::MissingFile).collect();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].file.as_deref(), Some("src/main.ts"));
+        assert_eq!(missing[0].template.as_deref(), Some("api"));
+
+        let unmatched: Vec<_> = report.for_check(Check::LanguagePattern).collect();
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!(unmatched[0].file.as_deref(), Some("notes.txt"));
+    }
+
+    #[test]
+    fn reports_empty_fields_and_unparsable_manifests() {
+        let tmp = registry("name: ''\ndescription: d\nversion: '1.0.0'\nfiles: [README.md]\n");
+        let report = validate_registry(tmp.path());
+        assert_eq!(checks(&report), vec![Check::Manifest]);
+        assert_eq!(report.diagnostics[0].message, "name is empty");
+
+        let tmp = registry("name: [\n");
+        let report = validate_registry(tmp.path());
+        assert_eq!(checks(&report), vec![Check::Manifest]);
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn reports_sdk_version_mismatches() {
+        let tmp = registry(
+            "name: API\ndescription: d\nversion: '1.0.0'\n\
+             files: [README.md, a/package.json, b/package.json]\n",
+        );
+        write(
+            tmp.path(),
+            "api/a/package.json",
+            r#"{"dependencies": {"iii-sdk": "^0.3.0"}}"#,
+        );
+        write(
+            tmp.path(),
+            "api/b/package.json",
+            r#"{"dependencies": {"iii-sdk": "^0.4.0"}}"#,
+        );
+
+        let report = validate_registry(tmp.path());
+        let mismatches: Vec<_> = report.for_check(Check::SdkVersion).collect();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].file.as_deref(), Some("b/package.json"));
+    }
+
+    #[test]
+    fn reports_missing_root_manifest_and_shared_sources() {
+        let tmp = tempfile::tempdir().unwrap();
+        let report = validate_registry(tmp.path());
+        assert_eq!(checks(&report), vec![Check::RootManifest]);
+
+        write(
+            tmp.path(),
+            "template.yaml",
+            "templates: []\nshared_files:\n  - source: missing.txt\n",
+        );
+        let report = validate_registry(tmp.path());
+        assert_eq!(
+            checks(&report),
+            vec![Check::RootManifest, Check::SharedSource]
+        );
+    }
+
+    #[test]
+    fn json_uses_kebab_case_checks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let json = serde_json::to_value(validate_registry(tmp.path())).unwrap();
+        assert_eq!(json["diagnostics"][0]["check"], "root-manifest");
+        assert_eq!(json["diagnostics"][0]["severity"], "error");
+    }
+
+    #[test]
+    fn sdk_version_parsers() {
+        assert_eq!(
+            cargo_sdk_version("[dependencies]\niii-sdk = \"0.3.0\"\n"),
+            Some("0.3.0".to_string())
+        );
+        assert_eq!(
+            pip_sdk_version("requests\niii-sdk==0.3.0\n"),
+            Some("iii-sdk==0.3.0".to_string())
+        );
+        assert_eq!(npm_sdk_version("{}"), None);
+    }
+}
@@ -0,0 +1,294 @@
+//! Extracting a single worker folder from a template into an existing project
+
+use crate::runtime::check::Language;
+use crate::templates::manifest::{
+    parse_option_assignment, parse_variable_assignment, OptionValues, TemplateFile,
+    TemplateManifest, VariableValues,
+};
+use anyhow::Result;
+
+/// Name of the worker manifest file inside each worker directory
+pub const WORKER_MANIFEST: &str = "iii.worker.yaml";
+
+/// Directory workers live in, both in templates and in generated projects
+pub const WORKERS_DIR: &str = "workers";
+
+/// Split a `<template>/<worker-dir>` reference into the template name and worker directory
+///
+/// The `workers/` prefix is optional: `quickstart/math-worker` and
+/// `quickstart/workers/math-worker` both resolve to `workers/math-worker`.
+pub fn parse_worker_source(from: &str) -> Result<(String, String)> {
+    let trimmed = from.trim().trim_matches('/');
+    let Some((template, worker)) = trimmed.split_once('/') else {
+        anyhow::bail!(
+            "Invalid worker source '{}'. Expected <template>/<worker-dir>, e.g. quickstart/math-worker",
+            from
+        );
+    };
+
+    let worker_dir = if worker.starts_with(&format!("{}/", WORKERS_DIR)) {
+        worker.to_string()
+    } else {
+        format!("{}/{}", WORKERS_DIR, worker)
+    };
+
+    Ok((template.to_string(), worker_dir))
+}
+
+/// Worker directories in a template (directories holding an `iii.worker.yaml`)
+pub fn available_workers(manifest: &TemplateManifest) -> Vec<String> {
+    manifest
+        .files
+        .iter()
+        .filter_map(|f| f.destination().strip_suffix(WORKER_MANIFEST))
+        .map(|dir| dir.trim_end_matches('/').to_string())
+        .filter(|dir| !dir.is_empty())
+        .collect()
+}
+
+/// Narrow a template manifest down to one worker directory, renamed into `workers/<new_name>`
+pub fn worker_manifest(
+    manifest: &TemplateManifest,
+    worker_dir: &str,
+    new_name: &str,
+) -> Result<TemplateManifest> {
+    let prefix = format!("{}/", worker_dir.trim_end_matches('/'));
+    let target_prefix = format!("{}/{}/", WORKERS_DIR, new_name);
+
+    let files: Vec<TemplateFile> = manifest
+        .files
+        .iter()
+        .filter_map(|file| {
+            let rest = file.destination().strip_prefix(&prefix)?;
+            Some(TemplateFile {
+                dest: Some(format!("{}{}", target_prefix, rest)),
+                ..file.clone()
+            })
+        })
+        .collect();
+
+    if !files
+        .iter()
+        .any(|f| f.destination().ends_with(WORKER_MANIFEST))
+    {
+        let available = available_workers(manifest);
+        anyhow::bail!(
+            "Worker '{}' not found in template '{}'. Available workers: {}",
+            worker_dir,
+            manifest.name,
+            if available.is_empty() {
+                "(none)".to_string()
+            } else {
+                available.join(", ")
+            }
+        );
+    }
+
+    let mut narrowed = manifest.clone();
+    narrowed.files = files;
+    narrowed.post_create.clear();
+    Ok(narrowed)
+}
+
+/// Rewrite the top-level `name:` field of an `iii.worker.yaml`, keeping the rest untouched
+pub fn rename_worker(content: &str, new_name: &str) -> String {
+    let mut renamed = false;
+    let mut output: Vec<String> = content
+        .lines()
+        .map(|line| {
+            if !renamed && line.starts_with("name:") {
+                renamed = true;
+                format!("name: {}", new_name)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    if !renamed {
+        output.insert(0, format!("name: {}", new_name));
+    }
+
+    let mut result = output.join("\n");
+    if content.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Read the worker's language from `runtime.language` in its `iii.worker.yaml`
+pub fn worker_language(content: &str) -> Option<Language> {
+    let value: serde_yaml::Value = serde_yaml::from_str(content).ok()?;
+    let language = value.get("runtime")?.get("language")?.as_str()?;
+    Language::parse(language)
+}
+
+/// Option values to copy a worker with
+///
+/// Starts from the values the project recorded when it was created from the same template
+/// (names the template no longer has are dropped), then applies `--option` assignments;
+/// anything left falls back to the option's default.
+pub fn worker_options(
+    manifest: &TemplateManifest,
+    recorded: Option<&OptionValues>,
+    assignments: &[String],
+) -> Result<OptionValues> {
+    let mut answers: OptionValues = recorded
+        .into_iter()
+        .flatten()
+        .filter(|(name, _)| manifest.option(name).is_some())
+        .map(|(name, value)| (name.clone(), *value))
+        .collect();
+    for assignment in assignments {
+        let (name, value) = parse_option_assignment(assignment)?;
+        answers.insert(name, value);
+    }
+    manifest.resolve_options(&answers)
+}
+
+/// Variable values to copy a worker with, built like [`worker_options`] from the recorded
+/// values and `--var` assignments; a variable left without a value is an error
+pub fn worker_variables(
+    manifest: &TemplateManifest,
+    recorded: Option<&VariableValues>,
+    assignments: &[String],
+) -> Result<VariableValues> {
+    let mut answers: VariableValues = recorded
+        .into_iter()
+        .flatten()
+        .filter(|(name, _)| manifest.variable(name).is_some())
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    for assignment in assignments {
+        let (name, value) = parse_variable_assignment(assignment)?;
+        answers.insert(name, value);
+    }
+    manifest.resolve_variables(&answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quickstart_manifest() -> TemplateManifest {
+        serde_yaml::from_str(
+            "name: Quickstart\ndescription: Test\nversion: '0.1.0'\nfiles:\n  - README.md\n  - workers/math-worker/iii.worker.yaml\n  - workers/math-worker/math_worker.py\n  - workers/caller-worker/iii.worker.yaml\n  - workers/caller-worker/src/worker.ts\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_worker_source_adds_workers_prefix() {
+        assert_eq!(
+            parse_worker_source("quickstart/math-worker").unwrap(),
+            ("quickstart".to_string(), "workers/math-worker".to_string())
+        );
+        assert_eq!(
+            parse_worker_source("quickstart/workers/math-worker/").unwrap(),
+            ("quickstart".to_string(), "workers/math-worker".to_string())
+        );
+        assert!(parse_worker_source("quickstart").is_err());
+    }
+
+    #[test]
+    fn worker_manifest_maps_files_into_new_directory() {
+        let narrowed =
+            worker_manifest(&quickstart_manifest(), "workers/math-worker", "billing").unwrap();
+
+        let mapping: Vec<(&str, &str)> = narrowed
+            .files
+            .iter()
+            .map(|f| (f.path.as_str(), f.destination()))
+            .collect();
+        assert_eq!(
+            mapping,
+            vec![
+                (
+                    "workers/math-worker/iii.worker.yaml",
+                    "workers/billing/iii.worker.yaml"
+                ),
+                (
+                    "workers/math-worker/math_worker.py",
+                    "workers/billing/math_worker.py"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn worker_manifest_lists_available_workers_when_missing() {
+        let err = worker_manifest(&quickstart_manifest(), "workers/nope", "nope")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("workers/math-worker"));
+        assert!(err.contains("workers/caller-worker"));
+    }
+
+    #[test]
+    fn rename_worker_rewrites_top_level_name_only() {
+        let content = "name: math-worker\nruntime:\n  language: python\n  name: keep\n";
+        assert_eq!(
+            rename_worker(content, "billing"),
+            "name: billing\nruntime:\n  language: python\n  name: keep\n"
+        );
+    }
+
+    #[test]
+    fn worker_options_start_from_recorded_values() {
+        let manifest: TemplateManifest = serde_yaml::from_str(
+            "name: Quickstart\ndescription: Test\nversion: '0.1.0'\noptions:\n  - name: docker\n  - name: redis\n  - name: tracing\n    default: true\n",
+        )
+        .unwrap();
+        let recorded = OptionValues::from([
+            ("docker".to_string(), true),
+            ("redis".to_string(), true),
+            ("dropped".to_string(), true),
+        ]);
+
+        let options =
+            worker_options(&manifest, Some(&recorded), &["redis=false".to_string()]).unwrap();
+        assert_eq!(
+            options,
+            OptionValues::from([
+                ("docker".to_string(), true),
+                ("redis".to_string(), false),
+                ("tracing".to_string(), true),
+            ])
+        );
+
+        assert!(worker_options(&manifest, None, &["nope".to_string()]).is_err());
+    }
+
+    #[test]
+    fn worker_variables_start_from_recorded_values() {
+        let manifest: TemplateManifest = serde_yaml::from_str(
+            "name: Quickstart\ndescription: Test\nversion: '0.1.0'\nvariables:\n  - name: service\n  - name: port\n    default: '3000'\n  - name: region\n",
+        )
+        .unwrap();
+        let recorded = VariableValues::from([
+            ("service".to_string(), "billing".to_string()),
+            ("dropped".to_string(), "x".to_string()),
+        ]);
+
+        let variables =
+            worker_variables(&manifest, Some(&recorded), &["region=eu".to_string()]).unwrap();
+        assert_eq!(
+            variables,
+            VariableValues::from([
+                ("port".to_string(), "3000".to_string()),
+                ("region".to_string(), "eu".to_string()),
+                ("service".to_string(), "billing".to_string()),
+            ])
+        );
+
+        let err = worker_variables(&manifest, Some(&recorded), &[]).unwrap_err();
+        assert!(err.to_string().contains("--var region=VALUE"), "{}", err);
+    }
+
+    #[test]
+    fn worker_language_reads_runtime_language() {
+        let content = "name: math-worker\nruntime:\n  language: python\n";
+        assert_eq!(worker_language(content), Some(Language::Python));
+        assert_eq!(worker_language("name: x\n"), None);
+    }
+}
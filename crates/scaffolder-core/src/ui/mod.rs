@@ -0,0 +1,27 @@
+//! Terminal prompts and logs used by the commands
+//!
+//! With the `tui` feature these are cliclack's Charm-style inline prompts; without it, the
+//! line-based equivalents in [`plain`]. The commands only go through this module, so both
+//! builds run the same flows.
+
+pub mod plain;
+mod progress;
+mod prompter;
+
+#[cfg(feature = "tui")]
+pub(crate) use cliclack::{
+    confirm, input, intro, log, multiselect, outro, select, spinner, ProgressBar,
+};
+#[cfg(not(feature = "tui"))]
+pub(crate) use plain::{
+    confirm, input, intro, log, multiselect, outro, select, spinner, ProgressBar,
+};
+
+pub use progress::TerminalEvents;
+pub use prompter::TerminalPrompter;
+
+/// Make the cursor visible again in case a prompt was interrupted while hiding it
+pub fn show_cursor() {
+    #[cfg(feature = "tui")]
+    let _ = console::Term::stderr().show_cursor();
+}
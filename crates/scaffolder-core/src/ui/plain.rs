@@ -0,0 +1,529 @@
+//! Line-based prompts and logs, used when the `tui` feature is off
+//!
+//! Mirrors the part of cliclack's API the commands use, so [`crate::ui`] can switch between
+//! the two. Questions and log lines go to stderr, like cliclack's, so stdout stays free for
+//! `--output json`; answers are read from stdin one line at a time. Nothing moves the cursor
+//! or adds colour, so the output reads fine in container logs and CI.
+
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+fn write_line(text: impl Display) -> io::Result<()> {
+    writeln!(io::stderr(), "{}", text)
+}
+
+/// Read one answer, failing instead of waiting forever once stdin is closed
+fn read_answer(input: &mut impl BufRead, prompt: &str) -> io::Result<String> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("No answer for \"{}\": input is closed", prompt),
+        ));
+    }
+    Ok(line.trim().to_string())
+}
+
+pub fn intro(title: impl Display) -> io::Result<()> {
+    write_line(title)
+}
+
+pub fn outro(message: impl Display) -> io::Result<()> {
+    write_line(message)
+}
+
+pub mod log {
+    use super::write_line;
+    use std::fmt::Display;
+    use std::io;
+
+    pub fn info(text: impl Display) -> io::Result<()> {
+        write_line(text)
+    }
+
+    pub fn success(message: impl Display) -> io::Result<()> {
+        write_line(message)
+    }
+
+    pub fn warning(message: impl Display) -> io::Result<()> {
+        write_line(format_args!("warning: {}", message))
+    }
+
+    pub fn error(message: impl Display) -> io::Result<()> {
+        write_line(format_args!("error: {}", message))
+    }
+}
+
+/// A step in progress; prints its start and stop messages as lines
+#[derive(Debug, Default)]
+pub struct ProgressBar;
+
+impl ProgressBar {
+    pub fn start(&self, message: impl Display) {
+        let _ = write_line(message);
+    }
+
+    pub fn stop(&self, message: impl Display) {
+        let _ = write_line(message);
+    }
+}
+
+pub fn spinner() -> ProgressBar {
+    ProgressBar
+}
+
+type Validator = Box<dyn Fn(&String) -> Result<(), String>>;
+
+/// A free-text question; an empty answer takes the default
+pub struct Input {
+    prompt: String,
+    placeholder: Option<String>,
+    default: Option<String>,
+    required: bool,
+    validators: Vec<Validator>,
+}
+
+pub fn input(prompt: impl Display) -> Input {
+    Input {
+        prompt: prompt.to_string(),
+        placeholder: None,
+        default: None,
+        required: true,
+        validators: Vec::new(),
+    }
+}
+
+impl Input {
+    /// Example shown next to the question when there is no default
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = Some(placeholder.to_string());
+        self
+    }
+
+    pub fn default_input(mut self, value: &str) -> Self {
+        self.default = Some(value.to_string());
+        self
+    }
+
+    /// Whether an empty answer is refused (the default)
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Ask again, showing the error, until `validator` accepts the answer
+    pub fn validate<V, E>(mut self, validator: V) -> Self
+    where
+        V: Fn(&String) -> Result<(), E> + 'static,
+        E: ToString,
+    {
+        self.validators.push(Box::new(move |input| {
+            validator(input).map_err(|e| e.to_string())
+        }));
+        self
+    }
+
+    pub fn interact<T: FromStr>(&mut self) -> io::Result<T> {
+        self.interact_with(&mut io::stdin().lock(), &mut io::stderr())
+    }
+
+    fn interact_with<T: FromStr>(
+        &self,
+        input: &mut impl BufRead,
+        output: &mut impl Write,
+    ) -> io::Result<T> {
+        loop {
+            match (&self.default, &self.placeholder) {
+                (Some(default), _) => write!(output, "{} [{}]: ", self.prompt, default)?,
+                (None, Some(placeholder)) => {
+                    write!(output, "{} (e.g. {}): ", self.prompt, placeholder)?
+                }
+                (None, None) => write!(output, "{}: ", self.prompt)?,
+            }
+            output.flush()?;
+
+            let mut answer = read_answer(input, &self.prompt)?;
+            if answer.is_empty() {
+                answer = self.default.clone().unwrap_or_default();
+            }
+            if answer.is_empty() && self.required {
+                writeln!(output, "A value is required")?;
+                continue;
+            }
+            if let Some(error) = self.validators.iter().find_map(|v| v(&answer).err()) {
+                writeln!(output, "{}", error)?;
+                continue;
+            }
+            match answer.parse() {
+                Ok(value) => return Ok(value),
+                Err(_) => writeln!(output, "Invalid value: {}", answer)?,
+            }
+        }
+    }
+}
+
+struct Item<T> {
+    value: T,
+    label: String,
+    hint: String,
+}
+
+fn write_items<T>(
+    output: &mut impl Write,
+    items: &[Item<T>],
+    marker: impl Fn(usize) -> &'static str,
+) -> io::Result<()> {
+    for (idx, item) in items.iter().enumerate() {
+        write!(output, "  {}{}. {}", marker(idx), idx + 1, item.label)?;
+        if !item.hint.is_empty() {
+            write!(output, " - {}", item.hint)?;
+        }
+        writeln!(output)?;
+    }
+    Ok(())
+}
+
+/// 1-based item number to index
+fn parse_choice(answer: &str, len: usize) -> Option<usize> {
+    match answer.parse::<usize>() {
+        Ok(n) if (1..=len).contains(&n) => Some(n - 1),
+        _ => None,
+    }
+}
+
+/// Pick one item by its number
+pub struct Select<T> {
+    prompt: String,
+    items: Vec<Item<T>>,
+    initial_value: Option<T>,
+}
+
+pub fn select<T: Clone + Eq>(prompt: impl Display) -> Select<T> {
+    Select {
+        prompt: prompt.to_string(),
+        items: Vec::new(),
+        initial_value: None,
+    }
+}
+
+impl<T: Clone + Eq> Select<T> {
+    pub fn item(mut self, value: T, label: impl Display, hint: impl Display) -> Self {
+        self.items.push(Item {
+            value,
+            label: label.to_string(),
+            hint: hint.to_string(),
+        });
+        self
+    }
+
+    pub fn initial_value(mut self, value: T) -> Self {
+        self.initial_value = Some(value);
+        self
+    }
+
+    pub fn interact(&mut self) -> io::Result<T> {
+        self.interact_with(&mut io::stdin().lock(), &mut io::stderr())
+    }
+
+    fn interact_with(&self, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<T> {
+        if self.items.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "No items added to the list",
+            ));
+        }
+        let default = self
+            .initial_value
+            .as_ref()
+            .and_then(|initial| self.items.iter().position(|i| &i.value == initial))
+            .unwrap_or(0);
+
+        writeln!(output, "{}", self.prompt)?;
+        write_items(output, &self.items, |_| "")?;
+        loop {
+            write!(output, "Choose 1-{} [{}]: ", self.items.len(), default + 1)?;
+            output.flush()?;
+
+            let answer = read_answer(input, &self.prompt)?;
+            let choice = if answer.is_empty() {
+                Some(default)
+            } else {
+                parse_choice(&answer, self.items.len())
+            };
+            match choice {
+                Some(idx) => return Ok(self.items[idx].value.clone()),
+                None => writeln!(output, "Enter a number from 1 to {}", self.items.len())?,
+            }
+        }
+    }
+}
+
+/// Pick any number of items by their numbers; an empty answer keeps the preselected ones
+pub struct MultiSelect<T> {
+    prompt: String,
+    items: Vec<Item<T>>,
+    initial_values: Vec<T>,
+    required: bool,
+}
+
+pub fn multiselect<T: Clone + Eq>(prompt: impl Display) -> MultiSelect<T> {
+    MultiSelect {
+        prompt: prompt.to_string(),
+        items: Vec::new(),
+        initial_values: Vec::new(),
+        required: true,
+    }
+}
+
+impl<T: Clone + Eq> MultiSelect<T> {
+    pub fn item(mut self, value: T, label: impl Display, hint: impl Display) -> Self {
+        self.items.push(Item {
+            value,
+            label: label.to_string(),
+            hint: hint.to_string(),
+        });
+        self
+    }
+
+    pub fn initial_values(mut self, values: Vec<T>) -> Self {
+        self.initial_values = values;
+        self
+    }
+
+    /// Whether selecting nothing is refused (the default)
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    pub fn interact(&mut self) -> io::Result<Vec<T>> {
+        self.interact_with(&mut io::stdin().lock(), &mut io::stderr())
+    }
+
+    fn interact_with(
+        &self,
+        input: &mut impl BufRead,
+        output: &mut impl Write,
+    ) -> io::Result<Vec<T>> {
+        if self.items.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "No items added to the list",
+            ));
+        }
+        let preselected: Vec<usize> = (0..self.items.len())
+            .filter(|&idx| self.initial_values.contains(&self.items[idx].value))
+            .collect();
+
+        writeln!(output, "{}", self.prompt)?;
+        write_items(output, &self.items, |idx| {
+            if preselected.contains(&idx) {
+                "[x] "
+            } else {
+                "[ ] "
+            }
+        })?;
+        loop {
+            write!(
+                output,
+                "Numbers separated by commas, \"none\", or Enter to keep [x]: "
+            )?;
+            output.flush()?;
+
+            let answer = read_answer(input, &self.prompt)?;
+            let chosen: Option<Vec<usize>> = if answer.is_empty() {
+                Some(preselected.clone())
+            } else if answer.eq_ignore_ascii_case("none") {
+                Some(Vec::new())
+            } else {
+                answer
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|part| !part.is_empty())
+                    .map(|part| parse_choice(part, self.items.len()))
+                    .collect()
+            };
+            let Some(chosen) = chosen else {
+                writeln!(output, "Enter numbers from 1 to {}", self.items.len())?;
+                continue;
+            };
+            if chosen.is_empty() && self.required {
+                writeln!(output, "Select at least one item")?;
+                continue;
+            }
+
+            // Keep the listed order whatever order the numbers were typed in
+            return Ok((0..self.items.len())
+                .filter(|idx| chosen.contains(idx))
+                .map(|idx| self.items[idx].value.clone())
+                .collect());
+        }
+    }
+}
+
+/// A yes/no question; an empty answer takes the initial value
+pub struct Confirm {
+    prompt: String,
+    initial_value: bool,
+}
+
+pub fn confirm(prompt: impl Display) -> Confirm {
+    Confirm {
+        prompt: prompt.to_string(),
+        initial_value: false,
+    }
+}
+
+impl Confirm {
+    pub fn initial_value(mut self, initial_value: bool) -> Self {
+        self.initial_value = initial_value;
+        self
+    }
+
+    pub fn interact(&mut self) -> io::Result<bool> {
+        self.interact_with(&mut io::stdin().lock(), &mut io::stderr())
+    }
+
+    fn interact_with(&self, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<bool> {
+        let choices = if self.initial_value { "Y/n" } else { "y/N" };
+        loop {
+            write!(output, "{} [{}]: ", self.prompt, choices)?;
+            output.flush()?;
+
+            let answer = read_answer(input, &self.prompt)?.to_ascii_lowercase();
+            match answer.as_str() {
+                "" => return Ok(self.initial_value),
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                _ => writeln!(output, "Answer y or n")?,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(lines: &str) -> io::Cursor<Vec<u8>> {
+        io::Cursor::new(lines.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn input_uses_default_and_asks_again_until_valid() {
+        let prompt = input("Template version")
+            .default_input("0.1.0")
+            .validate(|v: &String| {
+                if v.contains('.') {
+                    Ok(())
+                } else {
+                    Err("Use a version like 1.2.3")
+                }
+            });
+        let mut output = Vec::new();
+
+        let value: String = prompt
+            .interact_with(&mut answers("\n"), &mut output)
+            .unwrap();
+        assert_eq!(value, "0.1.0");
+
+        let value: String = prompt
+            .interact_with(&mut answers("latest\n2.0.0\n"), &mut output)
+            .unwrap();
+        assert_eq!(value, "2.0.0");
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .contains("Use a version like 1.2.3"));
+    }
+
+    #[test]
+    fn input_fails_when_stdin_is_closed() {
+        let err = input("Project directory")
+            .interact_with::<String>(&mut answers(""), &mut Vec::new())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let value: String = input("Minimum version")
+            .required(false)
+            .interact_with(&mut answers("\n"), &mut Vec::new())
+            .unwrap();
+        assert_eq!(value, "");
+    }
+
+    #[test]
+    fn select_takes_a_number_or_the_initial_value() {
+        let prompt = select("Select a template")
+            .item("web", "Web", "A website")
+            .item("api", "API", "")
+            .initial_value("api");
+        let mut output = Vec::new();
+
+        assert_eq!(
+            prompt
+                .interact_with(&mut answers("\n"), &mut output)
+                .unwrap(),
+            "api"
+        );
+        assert_eq!(
+            prompt
+                .interact_with(&mut answers("3\n1\n"), &mut output)
+                .unwrap(),
+            "web"
+        );
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("  1. Web - A website\n  2. API\n"));
+        assert!(output.contains("Enter a number from 1 to 2"));
+    }
+
+    #[test]
+    fn multiselect_keeps_listed_order() {
+        let prompt = multiselect("Languages")
+            .item("ts", "TypeScript", "")
+            .item("py", "Python", "")
+            .item("rs", "Rust", "")
+            .initial_values(vec!["py"]);
+        let mut output = Vec::new();
+
+        assert_eq!(
+            prompt
+                .interact_with(&mut answers("\n"), &mut output)
+                .unwrap(),
+            vec!["py"]
+        );
+        assert_eq!(
+            prompt
+                .interact_with(&mut answers("3, 1\n"), &mut output)
+                .unwrap(),
+            vec!["ts", "rs"]
+        );
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .contains("  [ ] 1. TypeScript\n  [x] 2. Python\n"));
+
+        let optional = multiselect("Options")
+            .item("docker", "docker", "")
+            .required(false);
+        assert!(optional
+            .interact_with(&mut answers("none\n"), &mut Vec::new())
+            .unwrap()
+            .is_empty());
+        let required = multiselect("Options").item("docker", "docker", "");
+        assert_eq!(
+            required
+                .interact_with(&mut answers("none\n1\n"), &mut Vec::new())
+                .unwrap(),
+            vec!["docker"]
+        );
+    }
+
+    #[test]
+    fn confirm_defaults_to_the_initial_value() {
+        let prompt = confirm("Continue anyway?").initial_value(true);
+        assert!(prompt
+            .interact_with(&mut answers("\n"), &mut Vec::new())
+            .unwrap());
+        assert!(!prompt
+            .interact_with(&mut answers("maybe\nNo\n"), &mut Vec::new())
+            .unwrap());
+    }
+}
@@ -0,0 +1,164 @@
+//! Pipeline events rendered as spinners and log lines
+
+use crate::events::{Event, EventSink};
+use crate::ui;
+use colored::Colorize;
+use std::sync::Mutex;
+
+/// Shows each step of the pipeline on the terminal
+///
+/// Steps with a start and a finish event get a spinner; everything else becomes a log line.
+/// Output of hooks and installers is echoed to stderr.
+#[derive(Default)]
+pub struct TerminalEvents {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    spinner: Option<ui::ProgressBar>,
+    runtimes: Vec<String>,
+}
+
+impl TerminalEvents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn start(&self, message: &str) {
+        let spinner = ui::spinner();
+        spinner.start(message);
+        self.state().spinner = Some(spinner);
+    }
+
+    fn stop(&self, message: String) {
+        if let Some(spinner) = self.state().spinner.take() {
+            spinner.stop(message);
+        }
+    }
+
+    fn render(&self, event: Event) -> std::io::Result<()> {
+        match event {
+            // The template prompt already shows which template is used
+            Event::TemplateFetched { .. } => {}
+            Event::RenderStarted => self.start("Rendering template..."),
+            Event::TemplateRendered { files } => self.stop(format!("Rendered {} files", files)),
+            Event::FileSkipped { path } => ui::log::info(format!("Kept existing {}", path))?,
+            Event::FileBackedUp { backup, .. } => {
+                ui::log::info(format!("Backed up existing file to {}", backup))?
+            }
+            Event::WriteStarted { .. } => self.start("Creating project..."),
+            Event::FileWritten { .. } => {}
+            Event::ProjectWritten { project_dir, files } => self.stop(format!(
+                "Created {} files in {}",
+                files,
+                project_dir.display()
+            )),
+            Event::FileMerged { path, summary } => {
+                ui::log::info(format!("Merged into existing {}: {}", path, summary))?
+            }
+            Event::RuntimeCheckStarted => {
+                self.state().runtimes.clear();
+                self.start("Checking runtimes...");
+            }
+            Event::RuntimeDetected { runtime } => {
+                let line = if runtime.available {
+                    format!(
+                        "{} ({})",
+                        runtime.name,
+                        runtime.version.as_deref().unwrap_or("unknown")
+                    )
+                } else {
+                    format!("{} (not installed)", runtime.name)
+                };
+                self.state().runtimes.push(line);
+            }
+            Event::RuntimeCheckFinished { missing: None } => {
+                let runtimes = std::mem::take(&mut self.state().runtimes);
+                self.stop(format!("Detected runtimes: {}", runtimes.join(", ")));
+            }
+            Event::RuntimeCheckFinished {
+                missing: Some(missing),
+            } => {
+                self.stop("Missing runtimes".to_string());
+                ui::log::error(missing)?;
+            }
+            Event::HookStarted { command } | Event::ToolInstallStarted { command, .. } => {
+                eprintln!();
+                eprintln!("{} {}", "Running:".dimmed(), command.yellow());
+                eprintln!();
+            }
+            Event::HookFinished {
+                command,
+                error: None,
+                ..
+            } => {
+                eprintln!();
+                ui::log::success(format!("Ran: {}", command))?;
+            }
+            Event::HookFinished {
+                required,
+                error: Some(error),
+                ..
+            } => {
+                eprintln!();
+                if required {
+                    ui::log::error(error)?;
+                } else {
+                    ui::log::warning(error)?;
+                }
+            }
+            Event::ProcessOutput {
+                line,
+                stderr: false,
+            } => eprintln!("  {}", line),
+            Event::ProcessOutput { line, stderr: true } => eprintln!("  {}", line.yellow()),
+            Event::DependencyInstallStarted { .. } => {
+                self.start("Installing dependencies (when applicable)...")
+            }
+            Event::DependencyInstallFinished {
+                installs,
+                error: None,
+            } => {
+                self.stop("Dependency step finished".to_string());
+                for install in installs.iter().filter(|i| !i.success) {
+                    ui::log::warning(format!(
+                        "`{}` failed in {}; install dependencies manually",
+                        install.command, install.directory
+                    ))?;
+                }
+            }
+            Event::DependencyInstallFinished { error: Some(_), .. } => {
+                self.stop("Dependency installation failed".to_string())
+            }
+            Event::TelemetrySent { .. } => {}
+            Event::RolledBack { removed } => {
+                ui::log::warning(format!("Rolled back {} files created by this run", removed))?
+            }
+            Event::ToolInstallFinished { tool, error: None } => {
+                eprintln!();
+                ui::log::success(format!("{} installed successfully", tool))?;
+            }
+            Event::ToolInstallFinished {
+                error: Some(error), ..
+            } => {
+                eprintln!();
+                ui::log::error(error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl EventSink for TerminalEvents {
+    fn emit(&self, event: Event) {
+        // Progress output is best-effort; a broken terminal must not fail the run
+        let _ = self.render(event);
+    }
+}
@@ -0,0 +1,150 @@
+//! `Prompter` that asks on the terminal
+
+use crate::commands::OutputFormat;
+use crate::prompter::{Confirmation, ConflictChoice, Prompter, ToolAction};
+use crate::runtime::check::Language;
+use crate::runtime::tool::ToolConfig;
+use crate::templates::copier::{self, ConflictResolution};
+use crate::templates::manifest::{TemplateManifest, TemplateOption, TemplateVariable};
+use crate::ui;
+use anyhow::Result;
+use colored::Colorize;
+
+/// Asks every question interactively; diffs for conflicting files go through `output`
+pub struct TerminalPrompter {
+    output: OutputFormat,
+}
+
+impl TerminalPrompter {
+    pub fn new(output: OutputFormat) -> Self {
+        Self { output }
+    }
+}
+
+impl Prompter for TerminalPrompter {
+    fn tool_action(&mut self, tool: &ToolConfig, _install_command: &str) -> Result<ToolAction> {
+        Ok(ui::select("What would you like to do?")
+            .item(
+                ToolAction::Install,
+                format!("Install {} automatically", tool.display_name),
+                "",
+            )
+            .item(
+                ToolAction::OpenDocs,
+                format!("Open documentation ({})", tool.docs_url),
+                "",
+            )
+            .item(
+                ToolAction::Skip,
+                format!("Skip and continue without {}", tool.display_name),
+                "",
+            )
+            .interact()?)
+    }
+
+    fn confirm(&mut self, confirmation: Confirmation<'_>) -> Result<bool> {
+        Ok(ui::confirm(confirmation.message())
+            .initial_value(confirmation.default_answer())
+            .interact()?)
+    }
+
+    fn select_template(&mut self, templates: &[(String, TemplateManifest)]) -> Result<usize> {
+        // Build select prompt - use indices to avoid borrow issues
+        let mut select = ui::select("Select a template");
+        for (idx, (_, manifest)) in templates.iter().enumerate() {
+            select = select.item(idx, &manifest.name, &manifest.description);
+        }
+        Ok(select.interact()?)
+    }
+
+    fn project_directory(&mut self) -> Result<String> {
+        Ok(ui::input("Project directory")
+            .placeholder(".")
+            .default_input(".")
+            .interact()?)
+    }
+
+    fn select_languages(
+        &mut self,
+        prompt: &str,
+        selectable: &[Language],
+        preselected: &[Language],
+    ) -> Result<Vec<Language>> {
+        let mut multi = ui::multiselect(prompt);
+        for lang in selectable {
+            multi = multi.item(*lang, lang.display_name(), "");
+        }
+        Ok(multi
+            .initial_values(preselected.to_vec())
+            .required(false)
+            .interact()?)
+    }
+
+    fn select_options(&mut self, options: &[&TemplateOption]) -> Result<Vec<String>> {
+        let mut multi = ui::multiselect("Select template options");
+        let mut initial = Vec::new();
+        for option in options {
+            multi = multi.item(option.name.clone(), option.label(), "");
+            if option.default {
+                initial.push(option.name.clone());
+            }
+        }
+        Ok(multi.initial_values(initial).required(false).interact()?)
+    }
+
+    fn input_variable(&mut self, variable: &TemplateVariable) -> Result<String> {
+        let mut input = ui::input(variable.label());
+        if let Some(default) = &variable.default {
+            input = input.placeholder(default).default_input(default);
+        }
+        Ok(input.interact()?)
+    }
+
+    /// Show a diff for the existing file and ask what to do with it
+    fn resolve_conflict(
+        &mut self,
+        path: &str,
+        existing: &[u8],
+        new: &[u8],
+    ) -> Result<ConflictChoice> {
+        let output = self.output;
+        match copier::unified_diff(path, existing, new) {
+            Some(diff) => {
+                output.println("");
+                for line in diff.lines() {
+                    if line.starts_with("+++") || line.starts_with("---") {
+                        output.println(format!("  {}", line.bold()));
+                    } else if line.starts_with('+') {
+                        output.println(format!("  {}", line.green()));
+                    } else if line.starts_with('-') {
+                        output.println(format!("  {}", line.red()));
+                    } else if line.starts_with("@@") {
+                        output.println(format!("  {}", line.cyan()));
+                    } else {
+                        output.println(format!("  {}", line.dimmed()));
+                    }
+                }
+                output.println("");
+            }
+            None => ui::log::info(format!("{} is a binary file, no diff shown", path))?,
+        }
+
+        let choice: &str = ui::select(format!("{} already exists", path))
+            .item("overwrite", "Overwrite", "")
+            .item("skip", "Keep existing", "")
+            .item("backup", "Back up existing and overwrite", "")
+            .item("overwrite-all", "Overwrite this and all remaining", "")
+            .item("skip-all", "Keep this and all remaining", "")
+            .interact()?;
+
+        let resolution = match choice {
+            "skip" | "skip-all" => ConflictResolution::Skip,
+            "backup" => ConflictResolution::Backup,
+            _ => ConflictResolution::Overwrite,
+        };
+        Ok(ConflictChoice {
+            resolution,
+            apply_to_all: choice.ends_with("-all"),
+        })
+    }
+}
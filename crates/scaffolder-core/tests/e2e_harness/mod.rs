@@ -395,6 +395,7 @@ impl Scenario {
         self.manifest
             .files
             .iter()
+            .map(|f| f.destination())
             .filter(|f| f.ends_with("iii.worker.yaml"))
             .filter_map(|f| f.rsplit_once('/').map(|(dir, _)| dir.to_string()))
             .collect()
@@ -563,6 +564,10 @@ impl ScenarioBuilder {
             Language::Rust,
         ];
 
+        let options = manifest
+            .resolve_options(&Default::default())
+            .expect("resolve template options");
+
         let project_dir = TempDir::new().expect("create temp dir");
 
         copy_template(
@@ -571,6 +576,7 @@ impl ScenarioBuilder {
             &manifest,
             project_dir.path(),
             &all_languages,
+            &options,
             &lang_files,
         )
         .await
@@ -739,7 +745,13 @@ async fn install_python_deps_with_local_sdk(pip: &str, dir: &Path, sdk_path: &Pa
 
     run_cmd(
         pip,
-        &["install", "-q", "--no-cache-dir", "--force-reinstall", sdk_path.to_str().unwrap()],
+        &[
+            "install",
+            "-q",
+            "--no-cache-dir",
+            "--force-reinstall",
+            sdk_path.to_str().unwrap(),
+        ],
         dir,
     )
     .await;
@@ -66,9 +66,7 @@ async fn multi_worker_orchestration() {
         let status = resp.status();
         let b: serde_json::Value = resp.json().await.unwrap();
 
-        let has_errors = b["errors"]
-            .as_array()
-            .map_or(false, |a| !a.is_empty());
+        let has_errors = b["errors"].as_array().is_some_and(|a| !a.is_empty());
 
         if status == 200 && !has_errors {
             eprintln!(
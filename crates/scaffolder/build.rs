@@ -0,0 +1,25 @@
+//! Embeds a product definition when `SCAFFOLDER_EMBED_PRODUCT` names one at build time
+
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo::rustc-check-cfg=cfg(embedded_product)");
+    println!("cargo::rerun-if-env-changed=SCAFFOLDER_EMBED_PRODUCT");
+
+    let Some(path) = std::env::var_os("SCAFFOLDER_EMBED_PRODUCT") else {
+        return;
+    };
+    let path = PathBuf::from(path);
+    println!("cargo::rerun-if-changed={}", path.display());
+
+    let out = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"))
+        .join("product.yaml");
+    if let Err(e) = std::fs::copy(&path, &out) {
+        panic!(
+            "Failed to read product definition {}: {}",
+            path.display(),
+            e
+        );
+    }
+    println!("cargo::rustc-cfg=embedded_product");
+}
@@ -0,0 +1,76 @@
+//! Generic scaffolder - a branded CLI defined entirely by a product YAML file
+//!
+//! The product definition is taken from, in order:
+//! 1. the file named by `SCAFFOLDER_PRODUCT` at runtime
+//! 2. the definition embedded at build time (`SCAFFOLDER_EMBED_PRODUCT=<path> cargo build -p scaffolder`)
+//! 3. `<executable name>.product.yaml` next to the executable
+
+use anyhow::{Context, Result};
+use scaffolder_core::ProductDefinition;
+use std::path::{Path, PathBuf};
+
+/// CLI version
+pub const CLI_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Runtime override for the product definition file
+const PRODUCT_ENV: &str = "SCAFFOLDER_PRODUCT";
+
+#[cfg(embedded_product)]
+const EMBEDDED_PRODUCT: Option<&str> =
+    Some(include_str!(concat!(env!("OUT_DIR"), "/product.yaml")));
+#[cfg(not(embedded_product))]
+const EMBEDDED_PRODUCT: Option<&str> = None;
+
+/// `<dir>/<name>.product.yaml` for an executable at `<dir>/<name>[.exe]`
+fn sidecar_path(exe: &Path) -> Option<PathBuf> {
+    let name = exe.file_stem()?.to_string_lossy();
+    Some(exe.with_file_name(format!("{}.product.yaml", name)))
+}
+
+fn load_product() -> Result<ProductDefinition> {
+    if let Some(path) = std::env::var_os(PRODUCT_ENV) {
+        return ProductDefinition::load(Path::new(&path));
+    }
+    if let Some(content) = EMBEDDED_PRODUCT {
+        return ProductDefinition::from_yaml(content).context("Embedded product definition");
+    }
+
+    let sidecar = std::env::current_exe()
+        .ok()
+        .and_then(|exe| sidecar_path(&exe));
+    match sidecar {
+        Some(path) if path.is_file() => ProductDefinition::load(&path),
+        _ => anyhow::bail!(
+            "No product definition found. Set {} to a product YAML file, place {} next to the executable, or build with SCAFFOLDER_EMBED_PRODUCT",
+            PRODUCT_ENV,
+            sidecar
+                .as_deref()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "<name>.product.yaml".to_string())
+        ),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let config = load_product()?;
+    scaffolder_core::cli::main(config, CLI_VERSION).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sidecar_sits_next_to_the_executable() {
+        assert_eq!(
+            sidecar_path(Path::new("/usr/local/bin/acme")),
+            Some(PathBuf::from("/usr/local/bin/acme.product.yaml"))
+        );
+        assert_eq!(
+            sidecar_path(Path::new("C:/tools/acme.exe")),
+            Some(PathBuf::from("C:/tools/acme.product.yaml"))
+        );
+    }
+}